@@ -0,0 +1,146 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Derive macro for the `aerospike::AerospikeRecord` trait. Use it through the `derive` feature of
+//! the `aerospike` crate rather than depending on this crate directly.
+
+#![warn(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// Derive `aerospike::AerospikeRecord`, `aerospike::ToValue` and `aerospike::FromValue` for a
+/// struct with named fields. See the `aerospike::mapping` module for the supported attributes.
+#[proc_macro_derive(AerospikeRecord, attributes(aerospike))]
+pub fn derive_aerospike_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct Field {
+    ident: syn::Ident,
+    name: String,
+    skip: bool,
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<Field>> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "AerospikeRecord can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "AerospikeRecord can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut result = Vec::with_capacity(fields.len());
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let mut name = ident.to_string();
+        let mut skip = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("aerospike"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    name = value.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported aerospike attribute"))
+                }
+            })?;
+        }
+
+        result.push(Field { ident, name, skip });
+    }
+    Ok(result)
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let stored: Vec<&Field> = fields.iter().filter(|f| !f.skip).collect();
+    let names: Vec<&String> = stored.iter().map(|f| &f.name).collect();
+    let idents: Vec<&syn::Ident> = stored.iter().map(|f| &f.ident).collect();
+    let skipped: Vec<&syn::Ident> = fields.iter().filter(|f| f.skip).map(|f| &f.ident).collect();
+
+    Ok(quote! {
+        impl #impl_generics ::aerospike::AerospikeRecord for #ident #ty_generics #where_clause {
+            fn bin_names() -> ::std::vec::Vec<&'static str> {
+                vec![#(#names),*]
+            }
+
+            fn to_bins(&self) -> ::std::vec::Vec<::aerospike::Bin<'static>> {
+                vec![#(
+                    ::aerospike::Bin::new(#names, ::aerospike::ToValue::to_value(&self.#idents))
+                ),*]
+            }
+
+            fn from_record(record: &::aerospike::Record) -> ::aerospike::Result<Self> {
+                Ok(#ident {
+                    #(#idents: ::aerospike::mapping::bin_value(record, #names)?,)*
+                    #(#skipped: ::std::default::Default::default(),)*
+                })
+            }
+        }
+
+        impl #impl_generics ::aerospike::ToValue for #ident #ty_generics #where_clause {
+            fn to_value(&self) -> ::aerospike::Value {
+                #[allow(unused_mut)]
+                let mut map = ::std::collections::HashMap::new();
+                #(
+                    let value = ::aerospike::ToValue::to_value(&self.#idents);
+                    if value != ::aerospike::Value::Nil {
+                        map.insert(::aerospike::Value::from(#names), value);
+                    }
+                )*
+                ::aerospike::Value::HashMap(map)
+            }
+        }
+
+        impl #impl_generics ::aerospike::FromValue for #ident #ty_generics #where_clause {
+            fn from_value(value: &::aerospike::Value) -> ::aerospike::Result<Self> {
+                Ok(#ident {
+                    #(#idents: ::aerospike::mapping::map_value(value, #names)?,)*
+                    #(#skipped: ::std::default::Default::default(),)*
+                })
+            }
+        }
+    })
+}
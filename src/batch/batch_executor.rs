@@ -27,6 +27,7 @@ use crate::cluster::{Cluster, Node};
 use crate::commands::BatchReadCommand;
 use crate::errors::{Error, Result};
 use crate::policy::{BatchPolicy, Concurrency};
+use crate::trace;
 use crate::Key;
 
 pub struct BatchExecutor {
@@ -71,11 +72,14 @@ impl BatchExecutor {
         };
         let jobs = Arc::new(Mutex::new(jobs.iter_mut()));
         let last_err: Arc<Mutex<Option<Error>>> = Arc::default();
+        let span = trace::Span::current();
         self.thread_pool.scoped(|scope| {
             for _ in 0..threads {
                 let last_err = last_err.clone();
                 let jobs = jobs.clone();
+                let span = &span;
                 scope.execute(move || {
+                    let _entered = span.enter();
                     let next_job = || jobs.lock().next();
                     while let Some(cmd) = next_job() {
                         if let Err(err) = cmd.execute() {
@@ -138,7 +142,8 @@ impl<T> SharedSlice<T> {
     }
 
     pub fn get(&self, idx: usize) -> Option<&T> {
-        unsafe { (*self.value.get()).get(idx) }
+        let slice: &Vec<T> = unsafe { &*self.value.get() };
+        slice.get(idx)
     }
 
     // Like slice.get_mut but does not require a mutable reference!
@@ -147,7 +152,8 @@ impl<T> SharedSlice<T> {
     }
 
     pub fn len(&self) -> usize {
-        unsafe { (*self.value.get()).len() }
+        let slice: &Vec<T> = unsafe { &*self.value.get() };
+        slice.len()
     }
 
     pub fn into_inner(self) -> Result<Vec<T>> {
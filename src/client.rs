@@ -13,32 +13,51 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::HashMap;
+#[cfg(feature = "json")]
+use std::convert::TryFrom;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 use std::str;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use std::vec::Vec;
 
+use parking_lot::Mutex;
 use scoped_pool::Pool;
 
 use crate::batch::BatchExecutor;
 use crate::cluster::{Cluster, Node};
+use crate::commands::stream_command::RecordHandler;
 use crate::commands::{
     DeleteCommand, ExecuteUDFCommand, ExistsCommand, OperateCommand, QueryCommand, ReadCommand,
     ScanCommand, TouchCommand, WriteCommand,
 };
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::info::{
+    self, BinInfo, BuildInfo, ConfigChange, ConfigResult, JobInfo, NamespaceConfig, NamespaceInfo,
+    NodeStatistics, SetConfig, SetInfo,
+};
+use crate::mapping::AerospikeRecord;
+use crate::metrics::{ClientStats, CommandType};
 use crate::net::ToHosts;
 use crate::operations::{Operation, OperationType};
-use crate::policy::{BatchPolicy, ClientPolicy, QueryPolicy, ReadPolicy, ScanPolicy, WritePolicy};
-use crate::task::{IndexTask, RegisterTask};
+use crate::policy::{
+    BatchPolicy, ClientPolicy, Priority, QueryPolicy, ReadPolicy, ScanPolicy, WritePolicy,
+};
+use crate::query::{ControlFlow, JobType};
+use crate::task::{IndexTask, RegisterTask, Task, TruncateTask};
+use crate::trace;
 use crate::{
-    BatchRead, Bin, Bins, CollectionIndexType, IndexType, Key, Record, Recordset, ResultCode,
-    Statement, UDFLang, Value,
+    BatchRead, Bin, Bins, CollectionIndexType, IndexType, Key, Record, RecordRef, Recordset,
+    ResultCode, Statement, UDFLang, Value,
 };
 
+// Time limit for `delete_set` to wait for the truncation if the policy has no total timeout.
+const DELETE_SET_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Instantiate a Client instance to access an Aerospike database cluster and perform database
 /// operations.
 ///
@@ -97,12 +116,16 @@ impl Client {
     pub fn new(policy: &ClientPolicy, hosts: &dyn ToHosts) -> Result<Self> {
         let hosts = hosts.to_hosts()?;
         let cluster = Cluster::new(policy.clone(), &hosts)?;
+        Ok(Client::with_cluster(policy, cluster))
+    }
+
+    pub(crate) fn with_cluster(policy: &ClientPolicy, cluster: Arc<Cluster>) -> Self {
         let thread_pool = Pool::new(policy.thread_pool_size);
 
-        Ok(Client {
+        Client {
             cluster,
             thread_pool,
-        })
+        }
     }
 
     /// Closes the connection to the Aerospike cluster.
@@ -136,6 +159,104 @@ impl Client {
         self.cluster.nodes()
     }
 
+    /// Opens connections until the connection pool of every active node holds at least `count`
+    /// connections, capped by `ClientPolicy::max_conns_per_node`. Returns the number of
+    /// connections opened. Use it to fill the pools before the client starts serving traffic,
+    /// e.g. in a readiness probe.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// match client.warm_up(16) {
+    ///     Ok(opened) => println!("Opened {} connections", opened),
+    ///     Err(err) => println!("Failed to warm up connection pools: {}", err),
+    /// }
+    /// ```
+    pub fn warm_up(&self, count: usize) -> Result<usize> {
+        self.cluster.warm_up(count)
+    }
+
+    /// Returns a snapshot of the client-side state of the active cluster nodes, e.g. their error
+    /// rates.
+    pub fn stats(&self) -> ClientStats {
+        ClientStats::new(&self.cluster.nodes())
+    }
+
+    /// Send one or more info commands to the given cluster node and return the raw responses,
+    /// keyed by command. See <https://www.aerospike.com/docs/reference/info> for the list of
+    /// available commands.
+    ///
+    /// # Examples
+    ///
+    /// Query the server version of each node in the cluster.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// for node in client.nodes() {
+    ///     match client.info(&node, &["build", "edition"]) {
+    ///         Ok(response) => println!("{}: {:?}", node.name(), response),
+    ///         Err(err) => println!("Error sending info command: {}", err),
+    ///     }
+    /// }
+    /// ```
+    pub fn info(&self, node: &Node, commands: &[&str]) -> Result<HashMap<String, String>> {
+        node.info(self.cluster.client_policy().timeout, commands)
+    }
+
+    /// Return the statistics of a namespace on the given node.
+    pub fn namespace_info(&self, node: &Node, namespace: &str) -> Result<NamespaceInfo> {
+        let cmd = format!("namespace/{}", namespace);
+        let response = self.info_value(node, &cmd)?;
+        Ok(NamespaceInfo::parse(namespace, &response))
+    }
+
+    /// Return the configuration of a namespace on the given node.
+    pub fn namespace_config(&self, node: &Node, namespace: &str) -> Result<NamespaceConfig> {
+        let cmd = format!("get-config:context=namespace;id={}", namespace);
+        let response = self.info_value(node, &cmd)?;
+        Ok(NamespaceConfig::parse(namespace, &response))
+    }
+
+    /// Return the statistics of all sets in a namespace on the given node.
+    pub fn set_info(&self, node: &Node, namespace: &str) -> Result<Vec<SetInfo>> {
+        let cmd = format!("sets/{}", namespace);
+        let response = self.info_value(node, &cmd)?;
+        Ok(SetInfo::parse_list(&response))
+    }
+
+    /// Return the bin names in use in a namespace on the given node.
+    pub fn bin_info(&self, node: &Node, namespace: &str) -> Result<BinInfo> {
+        let cmd = format!("bins/{}", namespace);
+        let response = self.info_value(node, &cmd)?;
+        Ok(BinInfo::parse(namespace, &response))
+    }
+
+    /// Return the server version of the given node.
+    pub fn build_info(&self, node: &Node) -> Result<BuildInfo> {
+        let response = self.info_value(node, "build")?;
+        BuildInfo::parse(&response)
+    }
+
+    /// Return the node-wide statistics of the given node.
+    pub fn node_statistics(&self, node: &Node) -> Result<NodeStatistics> {
+        let response = self.info_value(node, "statistics")?;
+        Ok(NodeStatistics::parse(&response))
+    }
+
+    /// Check whether the cluster is stable from the point of view of the given node, i.e. the
+    /// cluster membership is settled and no migrations are pending. Returns the current cluster
+    /// key if the cluster is stable, and an error otherwise.
+    pub fn cluster_stable(&self, node: &Node) -> Result<String> {
+        self.info_value(node, "cluster-stable")
+    }
+
     /// Read record for the specified key. Depending on the bins value provided, all record bins,
     /// only selected record bins or only the record headers will be returned. The policy can be
     /// used to specify timeouts.
@@ -191,6 +312,36 @@ impl Client {
         Ok(command.record.unwrap())
     }
 
+    /// Read a record for the specified key and convert it to a struct implementing the
+    /// `AerospikeRecord` trait, e.g. using `#[derive(AerospikeRecord)]`. Only the bins the struct
+    /// is mapped to are read.
+    pub fn get_as<T: AerospikeRecord>(&self, policy: &ReadPolicy, key: &Key) -> Result<T> {
+        let bins = Bins::Some(T::bin_names().into_iter().map(String::from).collect());
+        let record = self.get(policy, key, bins)?;
+        T::from_record(&record)
+    }
+
+    /// Read a record for the specified key and return its bins as a JSON object, with CDT map and
+    /// list bins converted to nested JSON objects and arrays. Fails with an `InvalidValue` error if
+    /// a bin contains a value that has no JSON representation, e.g. a blob.
+    ///
+    /// Servers older than 5.6 have no boolean bin type and store boolean bins written by
+    /// `put_json` as integers, which are returned as JSON numbers (0 or 1).
+    #[cfg(feature = "json")]
+    pub fn get_json<T>(&self, policy: &ReadPolicy, key: &Key, bins: T) -> Result<serde_json::Value>
+    where
+        T: Into<Bins>,
+    {
+        let record = self.get(policy, key, bins)?;
+        let mut doc = serde_json::Map::new();
+        for (name, value) in record.bins {
+            let value = serde_json::Value::try_from(value)
+                .chain_err(|| format!("Error reading bin '{}'", name))?;
+            doc.insert(name, value);
+        }
+        Ok(serde_json::Value::Object(doc))
+    }
+
     /// Read multiple record for specified batch keys in one batch call. This method allows
     /// different namespaces/bins to be requested for each key in the batch. If the `BatchRead` key
     /// field is not found, the corresponding record field will be `None`. The policy can be used
@@ -230,6 +381,8 @@ impl Client {
         policy: &BatchPolicy,
         batch_reads: Vec<BatchRead<'a>>,
     ) -> Result<Vec<BatchRead<'a>>> {
+        let span = trace::Span::batch(batch_reads.len());
+        let _entered = span.enter();
         let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
         executor.execute_batch_read(policy, batch_reads)
     }
@@ -286,6 +439,48 @@ impl Client {
         command.execute()
     }
 
+    /// Write a struct implementing the `AerospikeRecord` trait, e.g. using
+    /// `#[derive(AerospikeRecord)]`, as record bins for the specified key. See `Client::put` for
+    /// details.
+    pub fn put_from<T: AerospikeRecord>(
+        &self,
+        policy: &WritePolicy,
+        key: &Key,
+        record: &T,
+    ) -> Result<()> {
+        let bins = record.to_bins();
+        self.put(policy, key, &bins)
+    }
+
+    /// Write a JSON document for the specified key. Each field of the top-level JSON object is
+    /// stored as a separate bin; nested objects and arrays are stored as CDT maps and lists. A
+    /// `null` field deletes the bin. See `Client::put` for details.
+    ///
+    /// Servers older than 5.6 have no boolean bin type; on these servers, top-level boolean fields
+    /// are stored as integers and read back by `get_json` as numbers. Booleans nested in objects
+    /// and arrays are not affected.
+    #[cfg(feature = "json")]
+    pub fn put_json(&self, policy: &WritePolicy, key: &Key, doc: &serde_json::Value) -> Result<()> {
+        let fields = match *doc {
+            serde_json::Value::Object(ref fields) => fields,
+            _ => bail!(ErrorKind::InvalidArgument(
+                "JSON document must be an object".to_string()
+            )),
+        };
+        let mut bins = Vec::with_capacity(fields.len());
+        for (name, value) in fields {
+            let value = Value::from(value);
+            if let Value::UInt(_) = value {
+                bail!(ErrorKind::InvalidArgument(format!(
+                    "Bin '{}': integers larger than i64::MAX are only supported in maps and lists",
+                    name
+                )));
+            }
+            bins.push(Bin::new(name, value));
+        }
+        self.put(policy, key, &bins)
+    }
+
     /// Add integer bin values to existing record bin values. The policy specifies the transaction
     /// timeout, record expiration and how the transaction is handled when the record already
     /// exists. This call only works for integer values.
@@ -643,8 +838,13 @@ impl Client {
         T: Into<Bins>,
     {
         let bins = bins.into();
+        let span = trace::Span::fan_out(CommandType::Scan, namespace, set_name);
         let nodes = self.cluster.nodes();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, nodes.len()));
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            nodes.clone(),
+            JobType::Scan,
+        ));
         for node in nodes {
             let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
             let node = node.clone();
@@ -653,12 +853,23 @@ impl Client {
             let namespace = namespace.to_owned();
             let set_name = set_name.to_owned();
             let bins = bins.clone();
+            let span = span.clone();
 
             thread::spawn(move || {
+                let _entered = span.enter();
                 let mut command = ScanCommand::new(
-                    &policy, node, &namespace, &set_name, bins, recordset, partitions,
+                    &policy,
+                    node,
+                    &namespace,
+                    &set_name,
+                    bins,
+                    recordset,
+                    partitions,
+                    RecordHandler::Queue,
                 );
-                command.execute().unwrap();
+                if let Err(err) = command.execute() {
+                    command.push_error(err);
+                }
             });
         }
         Ok(recordset)
@@ -682,13 +893,19 @@ impl Client {
     {
         let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
         let bins = bins.into();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
+        let span = trace::Span::fan_out(CommandType::Scan, namespace, set_name);
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            vec![node.clone()],
+            JobType::Scan,
+        ));
         let t_recordset = recordset.clone();
         let policy = policy.to_owned();
         let namespace = namespace.to_owned();
         let set_name = set_name.to_owned();
 
         self.thread_pool.spawn(move || {
+            let _entered = span.enter();
             let mut command = ScanCommand::new(
                 &policy,
                 node,
@@ -697,8 +914,11 @@ impl Client {
                 bins,
                 t_recordset,
                 partitions,
+                RecordHandler::Queue,
             );
-            command.execute().unwrap();
+            if let Err(err) = command.execute() {
+                command.push_error(err);
+            }
         });
 
         Ok(recordset)
@@ -729,9 +949,18 @@ impl Client {
     pub fn query(&self, policy: &QueryPolicy, statement: Statement) -> Result<Arc<Recordset>> {
         statement.validate()?;
         let statement = Arc::new(statement);
+        let span = trace::Span::fan_out(
+            CommandType::Query,
+            &statement.namespace,
+            &statement.set_name,
+        );
 
         let nodes = self.cluster.nodes();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, nodes.len()));
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            nodes.clone(),
+            JobType::Query,
+        ));
         for node in nodes {
             let partitions = self
                 .cluster
@@ -740,11 +969,21 @@ impl Client {
             let t_recordset = recordset.clone();
             let policy = policy.to_owned();
             let statement = statement.clone();
+            let span = span.clone();
 
             self.thread_pool.spawn(move || {
-                let mut command =
-                    QueryCommand::new(&policy, node, statement, t_recordset, partitions);
-                command.execute().unwrap();
+                let _entered = span.enter();
+                let mut command = QueryCommand::new(
+                    &policy,
+                    node,
+                    statement,
+                    t_recordset,
+                    partitions,
+                    RecordHandler::Queue,
+                );
+                if let Err(err) = command.execute() {
+                    command.push_error(err);
+                }
             });
         }
         Ok(recordset)
@@ -760,8 +999,17 @@ impl Client {
         statement: Statement,
     ) -> Result<Arc<Recordset>> {
         statement.validate()?;
+        let span = trace::Span::fan_out(
+            CommandType::Query,
+            &statement.namespace,
+            &statement.set_name,
+        );
 
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            vec![node.clone()],
+            JobType::Query,
+        ));
         let t_recordset = recordset.clone();
         let policy = policy.to_owned();
         let statement = Arc::new(statement);
@@ -770,13 +1018,305 @@ impl Client {
             .node_partitions(node.as_ref(), &statement.namespace);
 
         self.thread_pool.spawn(move || {
-            let mut command = QueryCommand::new(&policy, node, statement, t_recordset, partitions);
-            command.execute().unwrap();
+            let _entered = span.enter();
+            let mut command = QueryCommand::new(
+                &policy,
+                node,
+                statement,
+                t_recordset,
+                partitions,
+                RecordHandler::Queue,
+            );
+            if let Err(err) = command.execute() {
+                command.push_error(err);
+            }
         });
 
         Ok(recordset)
     }
 
+    /// Read all records in the specified namespace and set and pass them to the callback. The
+    /// records are decoded in place from the buffer of the connection streaming them, without
+    /// allocating memory for each bin; use `RecordRef::to_record` to get an owned copy of a
+    /// record. The callback is invoked concurrently on the client's worker threads, one thread per
+    /// node, and can stop the scan by returning `ControlFlow::Break`, which aborts the scan on all
+    /// nodes. Blocks until the scan has completed or has been stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let adults = AtomicUsize::new(0);
+    /// client
+    ///     .scan_borrowed(&ScanPolicy::default(), "test", "demo", ["age"], |record| {
+    ///         if let Some(age) = record.get("age").and_then(|age| age.as_i64()) {
+    ///             if age >= 18 {
+    ///                 adults.fetch_add(1, Ordering::Relaxed);
+    ///             }
+    ///         }
+    ///         ControlFlow::Continue
+    ///     })
+    ///     .unwrap();
+    /// println!("{} adults", adults.load(Ordering::Relaxed));
+    /// ```
+    pub fn scan_borrowed<T, F>(
+        &self,
+        policy: &ScanPolicy,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+        callback: F,
+    ) -> Result<()>
+    where
+        T: Into<Bins>,
+        F: Fn(RecordRef) -> ControlFlow + Sync,
+    {
+        let handler = RecordHandler::Borrowed(&callback);
+        self.scan_with_handler(policy, namespace, set_name, bins.into(), handler)
+    }
+
+    /// Read all records in the specified namespace and set and pass them to the callback, without
+    /// queueing them on a `Recordset`. The callback is invoked concurrently on the client's worker
+    /// threads, one thread per node, as the records are parsed; records are only read from the
+    /// server as fast as the callback processes them. The callback can stop the scan by returning
+    /// `ControlFlow::Break`, which aborts the scan on all nodes. Blocks until the scan has
+    /// completed or has been stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// # use std::sync::Mutex;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let records = Mutex::new(vec![]);
+    /// client
+    ///     .scan_with(&ScanPolicy::default(), "test", "demo", Bins::All, |record| {
+    ///         let mut records = records.lock().unwrap();
+    ///         records.push(record);
+    ///         if records.len() < 100 {
+    ///             ControlFlow::Continue
+    ///         } else {
+    ///             ControlFlow::Break
+    ///         }
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn scan_with<T, F>(
+        &self,
+        policy: &ScanPolicy,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+        callback: F,
+    ) -> Result<()>
+    where
+        T: Into<Bins>,
+        F: Fn(Record) -> ControlFlow + Sync,
+    {
+        let handler = RecordHandler::Owned(&callback);
+        self.scan_with_handler(policy, namespace, set_name, bins.into(), handler)
+    }
+
+    fn scan_with_handler(
+        &self,
+        policy: &ScanPolicy,
+        namespace: &str,
+        set_name: &str,
+        bins: Bins,
+        handler: RecordHandler,
+    ) -> Result<()> {
+        let span = trace::Span::fan_out(CommandType::Scan, namespace, set_name);
+        let _entered = span.enter();
+        let nodes = self.cluster.nodes();
+        let recordset = Arc::new(Recordset::new(0, nodes.clone(), JobType::Scan));
+        let commands = nodes
+            .into_iter()
+            .map(|node| {
+                let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
+                ScanCommand::new(
+                    policy,
+                    node,
+                    namespace,
+                    set_name,
+                    bins.clone(),
+                    recordset.clone(),
+                    partitions,
+                    handler,
+                )
+            })
+            .collect();
+        self.execute_stream_commands(commands, ScanCommand::execute)
+    }
+
+    /// Execute a query on all server nodes and pass the resulting records to the callback. The
+    /// records are decoded in place, without allocating memory for each bin. See
+    /// `Client::scan_borrowed` for details.
+    pub fn query_borrowed<F>(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        callback: F,
+    ) -> Result<()>
+    where
+        F: Fn(RecordRef) -> ControlFlow + Sync,
+    {
+        self.query_with_handler(policy, statement, RecordHandler::Borrowed(&callback))
+    }
+
+    /// Execute a query on all server nodes and pass the resulting records to the callback,
+    /// without queueing them on a `Recordset`. See `Client::scan_with` for details.
+    pub fn query_with<F>(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        callback: F,
+    ) -> Result<()>
+    where
+        F: Fn(Record) -> ControlFlow + Sync,
+    {
+        self.query_with_handler(policy, statement, RecordHandler::Owned(&callback))
+    }
+
+    fn query_with_handler(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        handler: RecordHandler,
+    ) -> Result<()> {
+        statement.validate()?;
+        let span = trace::Span::fan_out(
+            CommandType::Query,
+            &statement.namespace,
+            &statement.set_name,
+        );
+        let _entered = span.enter();
+        let statement = Arc::new(statement);
+        let nodes = self.cluster.nodes();
+        let recordset = Arc::new(Recordset::new(0, nodes.clone(), JobType::Query));
+        let commands = nodes
+            .into_iter()
+            .map(|node| {
+                let partitions = self
+                    .cluster
+                    .node_partitions(node.as_ref(), &statement.namespace);
+                QueryCommand::new(
+                    policy,
+                    node,
+                    statement.clone(),
+                    recordset.clone(),
+                    partitions,
+                    handler,
+                )
+            })
+            .collect();
+        self.execute_stream_commands(commands, QueryCommand::execute)
+    }
+
+    // Executes the stream commands on the thread pool and waits for all of them to complete.
+    // Returns the last error reported by any of the commands.
+    fn execute_stream_commands<C, E>(&self, mut commands: Vec<C>, execute: E) -> Result<()>
+    where
+        C: Send,
+        E: Fn(&mut C) -> Result<()> + Sync,
+    {
+        let last_err: Mutex<Option<Error>> = Mutex::new(None);
+        let span = trace::Span::current();
+        self.thread_pool.scoped(|scope| {
+            for command in &mut commands {
+                let last_err = &last_err;
+                let execute = &execute;
+                let span = &span;
+                scope.execute(move || {
+                    let _entered = span.enter();
+                    if let Err(err) = execute(command) {
+                        *last_err.lock() = Some(err);
+                    }
+                });
+            }
+        });
+        match last_err.into_inner() {
+            None => Ok(()),
+            Some(err) => Err(err),
+        }
+    }
+
+    /// Return the scan and query jobs known to all cluster nodes, including jobs started by other
+    /// clients and jobs that have completed recently.
+    ///
+    /// # Examples
+    ///
+    /// List all running jobs.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// match client.jobs() {
+    ///     Ok(jobs) => {
+    ///         for job in jobs.iter().filter(|job| job.is_active()) {
+    ///             println!("{} {} on {}: {}% done", job.module, job.trid, job.node, job.progress);
+    ///         }
+    ///     }
+    ///     Err(err) => println!("Error listing jobs: {}", err),
+    /// }
+    /// ```
+    pub fn jobs(&self) -> Result<Vec<JobInfo>> {
+        let commands = [("scan", "scan-show"), ("query", "query-show")];
+        let mut jobs: Vec<JobInfo> = vec![];
+        for node in self.cluster.nodes() {
+            let response = self.info(&node, &["scan-show", "query-show"])?;
+            for (module, cmd) in &commands {
+                match response.get(*cmd) {
+                    Some(value) if info::check_response(cmd, value).is_ok() => {
+                        for job in JobInfo::parse_list(node.name(), module, value) {
+                            // Newer servers report scans as part of the queries.
+                            if !jobs
+                                .iter()
+                                .any(|j| j.node == job.node && j.trid == job.trid)
+                            {
+                                jobs.push(job);
+                            }
+                        }
+                    }
+                    value => debug!("Node {}: {} => {:?}", node, cmd, value),
+                }
+            }
+        }
+        Ok(jobs)
+    }
+
+    /// Abort the scan or query job with the given transaction ID on all cluster nodes. Returns an
+    /// error if none of the nodes knows the job, or if a node could not be reached.
+    pub fn kill_job(&self, trid: u64) -> Result<()> {
+        let commands = [
+            format!("query-kill:trid={}", trid),
+            format!("scan-abort:id={}", trid),
+        ];
+        self.send_job_cmd(trid, &commands)
+    }
+
+    /// Change the priority of the scan or query job with the given transaction ID on all cluster
+    /// nodes. Returns an error if none of the nodes knows the job, or if a node could not be
+    /// reached.
+    pub fn set_job_priority(&self, trid: u64, priority: &Priority) -> Result<()> {
+        let priority = priority.clone() as u8;
+        let commands = [
+            format!("query-set-priority:trid={};value={}", trid, priority),
+            format!(
+                "jobs:module=scan;cmd=set-priority;trid={};value={}",
+                trid, priority
+            ),
+        ];
+        self.send_job_cmd(trid, &commands)
+    }
+
     /// Removes all records in the specified namespace/set efficiently.
     ///
     /// This method is many orders of magnitude faster than deleting records one at a time. It
@@ -814,6 +1354,172 @@ impl Client {
             .chain_err(|| "Error truncating ns/set")
     }
 
+    /// Return the cluster-wide statistics of all sets in the specified namespace.
+    ///
+    /// The per-node statistics of every set are summed up across all cluster nodes. Object and
+    /// tombstone counts are divided by the namespace's effective replication factor, so that they
+    /// reflect the number of unique records in the set; memory and device usage include all
+    /// replicas. If the nodes report different replication factors, e.g. while the cluster is
+    /// being reconfigured, the highest one is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// match client.sets("test") {
+    ///     Ok(sets) => {
+    ///         for set in sets {
+    ///             println!("{}: {} records, {} bytes", set.set_name, set.objects,
+    ///                 set.memory_data_bytes + set.device_data_bytes);
+    ///         }
+    ///     }
+    ///     Err(err) => println!("Error listing sets: {}", err),
+    /// }
+    /// ```
+    pub fn sets(&self, namespace: &str) -> Result<Vec<SetInfo>> {
+        let nodes = self.cluster.nodes();
+        if nodes.is_empty() {
+            bail!(ErrorKind::Connection("No connected node".to_string()));
+        }
+
+        // while the replication factor is being changed, the nodes may report different ones
+        let mut replication_factor = 0;
+        for node in &nodes {
+            let node_factor = self.namespace_info(node, namespace)?.replication_factor;
+            replication_factor = replication_factor.max(node_factor);
+        }
+        let per_node = nodes
+            .iter()
+            .map(|node| self.set_info(node, namespace))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SetInfo::aggregate(per_node, replication_factor))
+    }
+
+    /// Change set-level configuration, such as `disable-eviction` or `stop-writes-count`, on all
+    /// cluster nodes. Only the settings specified in `config` are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// use aerospike::info::SetConfig;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let mut config = SetConfig::new();
+    /// config.disable_eviction = Some(true);
+    /// config.stop_writes_count = Some(1_000_000);
+    /// match client.configure_set(&WritePolicy::default(), "test", "demo", &config) {
+    ///     Ok(()) => println!("Set configuration updated"),
+    ///     Err(err) => println!("Error configuring set: {}", err),
+    /// }
+    /// ```
+    pub fn configure_set(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        config: &SetConfig,
+    ) -> Result<()> {
+        let nodes = self.cluster.nodes();
+        if nodes.is_empty() {
+            bail!(ErrorKind::Connection("No connected node".to_string()));
+        }
+
+        for cmd in config.commands(namespace, set_name) {
+            for node in &nodes {
+                self.send_info_cmd_to_node(node, &cmd, policy.base_policy.timeout)
+                    .chain_err(|| format!("Error configuring set on node {}", node))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a dynamic configuration change to all cluster nodes using the `set-config` info
+    /// command. After the change has been applied, the configuration is read back from each node
+    /// to verify that the new values are in effect. If the change is configured to roll back on
+    /// failure, the previous values are restored on all nodes if the change failed on any node.
+    ///
+    /// Returns the outcome of the change for each node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// use aerospike::info::ConfigChange;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let change = ConfigChange::namespace("test")
+    ///     .set("default-ttl", 3600)
+    ///     .rollback_on_failure(true);
+    /// match client.set_config(&WritePolicy::default(), &change) {
+    ///     Ok(results) => {
+    ///         for res in results {
+    ///             println!("{}: {:?}", res.node, res.result);
+    ///         }
+    ///     }
+    ///     Err(err) => println!("Error changing config: {}", err),
+    /// }
+    /// ```
+    pub fn set_config(
+        &self,
+        policy: &WritePolicy,
+        change: &ConfigChange,
+    ) -> Result<Vec<ConfigResult>> {
+        if change.params().is_empty() {
+            bail!(ErrorKind::InvalidArgument(
+                "No config parameters to change".to_string()
+            ));
+        }
+
+        let nodes = self.cluster.nodes();
+        if nodes.is_empty() {
+            bail!(ErrorKind::Connection("No connected node".to_string()));
+        }
+
+        Ok(info::config_change::apply(
+            &nodes,
+            change,
+            policy.base_policy.timeout,
+        ))
+    }
+
+    /// Removes all records in the specified set and waits until the truncation has completed on
+    /// all cluster nodes, i.e. until every node reports a truncate LUT for the set that is newer
+    /// than the one before the truncation. Records written after the truncation do not delay the
+    /// completion. The wait is limited to 30 seconds, or to the policy's total timeout if that is
+    /// longer.
+    pub fn delete_set(&self, policy: &WritePolicy, namespace: &str, set_name: &str) -> Result<()> {
+        if set_name.is_empty() {
+            bail!(ErrorKind::InvalidArgument(
+                "Set name must not be empty".to_string()
+            ));
+        }
+
+        let task = TruncateTask::new(
+            Arc::clone(&self.cluster),
+            namespace.to_string(),
+            set_name.to_string(),
+        )
+        .chain_err(|| "Error reading set truncation status")?;
+
+        self.truncate(policy, namespace, set_name, 0)?;
+
+        let timeout = policy
+            .base_policy
+            .timeout
+            .unwrap_or_default()
+            .max(DELETE_SET_TIMEOUT);
+        task.wait_till_complete(Some(timeout))
+            .chain_err(|| "Error verifying set deletion")?;
+        Ok(())
+    }
+
     /// Create a secondary index on a bin containing scalar values. This asynchronous server call
     /// returns before the command is complete.
     ///
@@ -907,9 +1613,65 @@ impl Client {
             .chain_err(|| "Error dropping index")
     }
 
+    // Sends the given alternative commands to every node, until one of them succeeds. The job is
+    // usually known to all nodes, but may already have completed on some of them. A node that
+    // rejects all commands does not know the job; errors communicating with a node are returned.
+    fn send_job_cmd(&self, trid: u64, commands: &[String]) -> Result<()> {
+        let timeout = self.cluster.client_policy().timeout;
+        let mut found = false;
+        for node in self.cluster.nodes() {
+            for cmd in commands {
+                match self.send_info_cmd_to_node(&node, cmd, timeout) {
+                    Ok(()) => {
+                        found = true;
+                        break;
+                    }
+                    Err(Error(ErrorKind::ServerError(_), _))
+                    | Err(Error(ErrorKind::BadResponse(_), _)) => {
+                        debug!("Node {}: job {} not found by `{}`", node, trid, cmd);
+                    }
+                    Err(err) => {
+                        return Err(err)
+                            .chain_err(|| format!("Failed to send job command to node {}", node));
+                    }
+                }
+            }
+        }
+
+        if !found {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Job {} not found",
+                trid
+            )));
+        }
+        Ok(())
+    }
+
+    fn info_value(&self, node: &Node, cmd: &str) -> Result<String> {
+        let mut response = self.info(node, &[cmd])?;
+        let value = match response.remove(cmd) {
+            Some(value) => value,
+            None => bail!(ErrorKind::BadResponse(format!(
+                "Missing response for info command `{}`",
+                cmd
+            ))),
+        };
+        info::check_response(cmd, &value)?;
+        Ok(value)
+    }
+
     fn send_info_cmd(&self, cmd: &str, policy: &WritePolicy) -> Result<()> {
         let node = self.cluster.get_random_node()?;
-        let response = node.info(policy.base_policy.timeout, &[cmd])?;
+        self.send_info_cmd_to_node(&node, cmd, policy.base_policy.timeout)
+    }
+
+    fn send_info_cmd_to_node(
+        &self,
+        node: &Node,
+        cmd: &str,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        let response = node.info(timeout, &[cmd])?;
 
         if let Some(v) = response.values().next() {
             if v.to_uppercase() == "OK" {
@@ -19,7 +19,7 @@ pub mod partition;
 pub mod partition_tokenizer;
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender, TryRecvError};
 use std::sync::Arc;
@@ -38,6 +38,7 @@ use self::partition_tokenizer::PartitionTokenizer;
 use crate::errors::{ErrorKind, Result};
 use crate::net::Host;
 use crate::policy::ClientPolicy;
+use crate::trace;
 
 // Cluster encapsulates the aerospike cluster nodes and manages
 // them.
@@ -60,26 +61,16 @@ pub struct Cluster {
 
     client_policy: ClientPolicy,
 
+    // Number of tends since the cluster was created; used to reset the node error rates.
+    tend_count: AtomicUsize,
+
     tend_channel: Mutex<Sender<()>>,
     closed: AtomicBool,
 }
 
 impl Cluster {
     pub fn new(policy: ClientPolicy, hosts: &[Host]) -> Result<Arc<Self>> {
-        let (tx, rx): (Sender<()>, Receiver<()>) = mpsc::channel();
-        let cluster = Arc::new(Cluster {
-            client_policy: policy,
-
-            seeds: Arc::new(RwLock::new(hosts.to_vec())),
-            aliases: Arc::new(RwLock::new(HashMap::new())),
-            nodes: Arc::new(RwLock::new(vec![])),
-
-            partition_write_map: Arc::new(RwLock::new(HashMap::new())),
-            node_index: AtomicIsize::new(0),
-
-            tend_channel: Mutex::new(tx),
-            closed: AtomicBool::new(false),
-        });
+        let (cluster, rx) = Cluster::unseeded(policy, hosts);
 
         // try to seed connections for first use
         Cluster::wait_till_stabilized(cluster.clone())?;
@@ -101,6 +92,33 @@ impl Cluster {
         Ok(cluster)
     }
 
+    // Cluster with the given nodes, which is neither seeded nor tended.
+    #[cfg(test)]
+    pub fn with_nodes(policy: ClientPolicy, nodes: Vec<Arc<Node>>) -> Arc<Self> {
+        let (cluster, _) = Cluster::unseeded(policy, &[]);
+        cluster.add_nodes(&nodes);
+        cluster
+    }
+
+    fn unseeded(policy: ClientPolicy, hosts: &[Host]) -> (Arc<Self>, Receiver<()>) {
+        let (tx, rx): (Sender<()>, Receiver<()>) = mpsc::channel();
+        let cluster = Arc::new(Cluster {
+            client_policy: policy,
+
+            seeds: Arc::new(RwLock::new(hosts.to_vec())),
+            aliases: Arc::new(RwLock::new(HashMap::new())),
+            nodes: Arc::new(RwLock::new(vec![])),
+
+            partition_write_map: Arc::new(RwLock::new(HashMap::new())),
+            node_index: AtomicIsize::new(0),
+            tend_count: AtomicUsize::new(0),
+
+            tend_channel: Mutex::new(tx),
+            closed: AtomicBool::new(false),
+        });
+        (cluster, rx)
+    }
+
     fn tend_thread(cluster: Arc<Cluster>, rx: Receiver<()>) {
         let tend_interval = cluster.client_policy.tend_interval;
 
@@ -131,6 +149,8 @@ impl Cluster {
     }
 
     fn tend(&self) -> Result<()> {
+        let span = trace::Span::tend();
+        let _entered = span.enter();
         let mut nodes = self.nodes();
 
         // All node additions/deletions are performed in tend thread.
@@ -144,8 +164,17 @@ impl Cluster {
         let mut friend_list: Vec<Host> = vec![];
         let mut refresh_count = 0;
 
+        // Reset the node error rates at the end of each error rate window.
+        let error_rate_window = self.client_policy.error_rate_window.max(1);
+        let reset_error_rate =
+            (self.tend_count.fetch_add(1, Ordering::Relaxed) + 1) % error_rate_window == 0;
+
         // Refresh all known nodes.
         for node in nodes {
+            if reset_error_rate {
+                node.reset_error_count();
+            }
+
             let old_gen = node.partition_generation();
             if node.is_active() {
                 match node.refresh(self.aliases()) {
@@ -178,6 +207,14 @@ impl Cluster {
         let remove_list = self.find_nodes_to_remove(refresh_count);
         self.remove_nodes_and_aliases(remove_list);
 
+        // Trim idle connections and keep the minimum number of connections open.
+        for node in self.nodes() {
+            if let Err(err) = node.balance_connections() {
+                warn!("Node `{}` failed to open connections: {}", node, err);
+            }
+        }
+
+        span.record_nodes(self.nodes().len());
         Ok(())
     }
 
@@ -411,6 +448,11 @@ impl Cluster {
     fn add_nodes_and_aliases(&self, friend_list: &[Arc<Node>]) {
         for node in friend_list {
             self.add_aliases(node.clone());
+
+            // pre-warm the connection pool of the new node
+            if let Err(err) = node.balance_connections() {
+                warn!("Node `{}` failed to open connections: {}", node, err);
+            }
         }
         self.add_nodes(friend_list);
     }
@@ -489,6 +531,16 @@ impl Cluster {
         self.aliases.read().clone()
     }
 
+    // Opens connections until the pool of every node holds at least `count` connections. Returns
+    // the number of connections opened.
+    pub fn warm_up(&self, count: usize) -> Result<usize> {
+        let mut opened = 0;
+        for node in self.nodes() {
+            opened += node.warm_up(count)?;
+        }
+        Ok(opened)
+    }
+
     pub fn nodes(&self) -> Vec<Arc<Node>> {
         self.nodes.read().clone()
     }
@@ -27,6 +27,7 @@ use parking_lot::RwLock;
 use crate::cluster::node_validator::NodeValidator;
 use crate::commands::Message;
 use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::metrics::PoolStats;
 use crate::net::{ConnectionPool, Host, PooledConnection};
 use crate::policy::ClientPolicy;
 
@@ -42,6 +43,8 @@ pub struct Node {
 
     connection_pool: ConnectionPool,
     failures: AtomicUsize,
+    error_count: AtomicUsize,
+    error_rate_trips: AtomicUsize,
 
     partition_generation: AtomicIsize,
     refresh_count: AtomicUsize,
@@ -54,6 +57,7 @@ pub struct Node {
     supports_batch_index: AtomicBool,
     supports_replicas_all: AtomicBool,
     supports_geo: AtomicBool,
+    supports_bool: AtomicBool,
 }
 
 impl Node {
@@ -68,6 +72,8 @@ impl Node {
             host: nv.aliases[0].clone(),
             connection_pool: ConnectionPool::new(nv.aliases[0].clone(), client_policy),
             failures: AtomicUsize::new(0),
+            error_count: AtomicUsize::new(0),
+            error_rate_trips: AtomicUsize::new(0),
             partition_generation: AtomicIsize::new(-1),
             refresh_count: AtomicUsize::new(0),
             reference_count: AtomicUsize::new(0),
@@ -78,6 +84,7 @@ impl Node {
             supports_batch_index: AtomicBool::new(nv.supports_batch_index),
             supports_replicas_all: AtomicBool::new(nv.supports_replicas_all),
             supports_geo: AtomicBool::new(nv.supports_geo),
+            supports_bool: AtomicBool::new(nv.supports_bool),
         }
     }
 
@@ -105,6 +112,10 @@ impl Node {
         self.supports_geo.load(Ordering::Relaxed)
     }
 
+    pub fn supports_bool(&self) -> bool {
+        self.supports_bool.load(Ordering::Relaxed)
+    }
+
     pub fn reference_count(&self) -> usize {
         self.reference_count.load(Ordering::Relaxed)
     }
@@ -246,6 +257,26 @@ impl Node {
         self.connection_pool.get(timeout)
     }
 
+    // Opens connections until the pool holds at least `count` connections. Returns the number
+    // of connections opened.
+    pub fn warm_up(&self, count: usize) -> Result<usize> {
+        self.connection_pool.warm_up(count)
+    }
+
+    // Closes idle connections above the minimum pool size and re-opens connections below it.
+    pub fn balance_connections(&self) -> Result<()> {
+        self.connection_pool.balance()
+    }
+
+    // Number of open connections to the node, both idle and in use.
+    pub fn connection_count(&self) -> usize {
+        self.connection_pool.num_conns()
+    }
+
+    pub fn pool_stats(&self) -> PoolStats {
+        self.connection_pool.stats()
+    }
+
     pub fn failures(&self) -> usize {
         self.failures.load(Ordering::Relaxed)
     }
@@ -258,6 +289,41 @@ impl Node {
         self.failures.fetch_add(1, Ordering::Relaxed)
     }
 
+    // Number of command errors on this node within the current error rate window.
+    pub fn error_count(&self) -> usize {
+        self.error_count.load(Ordering::Relaxed)
+    }
+
+    // Number of times the error count exceeded `max_error_rate` within a window.
+    pub fn error_rate_trips(&self) -> usize {
+        self.error_rate_trips.load(Ordering::Relaxed)
+    }
+
+    pub fn add_error(&self) {
+        let count = self.error_count.fetch_add(1, Ordering::Relaxed) + 1;
+        let max_error_rate = self.client_policy.max_error_rate;
+        if max_error_rate > 0 && count == max_error_rate + 1 {
+            self.error_rate_trips.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "Node {}: max. error rate of {} exceeded; rejecting commands until the error rate window resets",
+                self, max_error_rate
+            );
+        }
+    }
+
+    pub fn reset_error_count(&self) {
+        self.error_count.store(0, Ordering::Relaxed)
+    }
+
+    // Fails if the node exceeded the max. error rate within the current window.
+    pub fn validate_error_rate(&self) -> Result<()> {
+        let max_error_rate = self.client_policy.max_error_rate;
+        if max_error_rate > 0 && self.error_count() > max_error_rate {
+            bail!(ErrorKind::MaxErrorRate(self.name.clone()));
+        }
+        Ok(())
+    }
+
     fn inactivate(&self) {
         self.active.store(false, Ordering::Relaxed);
     }
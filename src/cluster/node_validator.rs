@@ -19,6 +19,7 @@ use std::vec::Vec;
 use crate::cluster::Cluster;
 use crate::commands::Message;
 use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::info::BuildInfo;
 use crate::net::{Connection, Host};
 use crate::policy::ClientPolicy;
 
@@ -35,6 +36,7 @@ pub struct NodeValidator {
     pub supports_batch_index: bool,
     pub supports_replicas_all: bool,
     pub supports_geo: bool,
+    pub supports_bool: bool,
 }
 
 // Generates a node validator
@@ -50,6 +52,7 @@ impl NodeValidator {
             supports_batch_index: false,
             supports_replicas_all: false,
             supports_geo: false,
+            supports_bool: false,
         }
     }
 
@@ -93,7 +96,7 @@ impl NodeValidator {
     fn validate_alias(&mut self, cluster: &Cluster, alias: &Host) -> Result<()> {
         let mut conn = Connection::new(&alias, &self.client_policy)?;
         conn.set_timeout(self.client_policy.timeout)?;
-        let info_map = Message::info(&mut conn, &["node", "cluster-name", "features"])?;
+        let info_map = Message::info(&mut conn, &["node", "cluster-name", "features", "build"])?;
 
         match info_map.get("node") {
             None => bail!(ErrorKind::InvalidNode(String::from("Missing node name"))),
@@ -118,6 +121,12 @@ impl NodeValidator {
             self.set_features(features);
         }
 
+        // Native boolean particles are supported since server version 5.6, which does not
+        // advertise them as feature.
+        if let Some(Ok(build)) = info_map.get("build").map(|build| BuildInfo::parse(build)) {
+            self.supports_bool = (build.major, build.minor) >= (5, 6);
+        }
+
         Ok(())
     }
 
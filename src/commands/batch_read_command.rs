@@ -20,10 +20,15 @@ use std::time::{Duration, Instant};
 use crate::batch::batch_executor::SharedSlice;
 use crate::cluster::Node;
 use crate::commands::{self, Command};
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::expressions::FilterExpression;
+use crate::interceptor;
+use crate::metrics::{self, CommandType};
 use crate::net::Connection;
-use crate::policy::{BatchPolicy, Policy, PolicyLike};
-use crate::{value, BatchRead, Record, ResultCode, Value};
+use crate::policy::ClientPolicy;
+use crate::policy::{BatchPolicy, Policy, PolicyLike, RetryContext};
+use crate::trace;
+use crate::{value, BatchRead, Key, Record, ResultCode, Value};
 
 struct BatchRecord {
     batch_index: usize,
@@ -53,58 +58,143 @@ impl<'a, 'b> BatchReadCommand<'a, 'b> {
     }
 
     pub fn execute(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let span = trace::Span::command(CommandType::Batch, None);
+        let _entered = span.enter();
+        let interceptors = self.node.client_policy().interceptors.clone();
+        let policy = self.policy;
+        let (filter, result) = match interceptor::before_command(&interceptors, policy, self, start)
+        {
+            Ok(filter) => {
+                let result = self.execute_attempts(filter.as_ref(), &span);
+                (filter, result)
+            }
+            Err(err) => (None, Err(err)),
+        };
+        span.record_result(&result);
+        metrics::record(&self.node, CommandType::Batch, start, &result);
+        let node = Some(self.node.clone());
+        interceptor::after_command(
+            &interceptors,
+            policy,
+            self,
+            filter.as_ref(),
+            node,
+            start,
+            &result,
+        );
+        result
+    }
+
+    fn execute_attempts(
+        &mut self,
+        filter: Option<&FilterExpression>,
+        span: &trace::Span,
+    ) -> Result<()> {
         let mut iterations = 0;
+        let mut last_err: Option<Error> = None;
         let base_policy = self.policy.base();
 
         // set timeout outside the loop
         let deadline = base_policy.deadline();
+        let retry_policy = base_policy.retry_policy();
+        let max_retries = base_policy.max_retries();
+
+        let retry = |err: &Error, node: Option<&Arc<Node>>, attempt: usize, sent: bool| {
+            let ctx = RetryContext {
+                attempt,
+                error: err,
+                node,
+                is_write: false,
+                sent,
+            };
+            commands::retry_attempt(retry_policy, max_retries, deadline, &ctx)
+        };
 
         // Execute command until successful, timed out or maximum iterations have been reached.
-        loop {
+        let err = loop {
             iterations += 1;
 
-            // too many retries
-            if let Some(max_retries) = base_policy.max_retries() {
-                if iterations > max_retries + 1 {
-                    bail!(ErrorKind::Connection(format!(
-                        "Timeout after {} tries",
-                        iterations
-                    )));
+            if retry_policy.is_none() {
+                // too many retries
+                if let Some(max_retries) = max_retries {
+                    if iterations > max_retries + 1 {
+                        iterations -= 1;
+                        break ErrorKind::Connection(format!("Timeout after {} tries", iterations));
+                    }
                 }
-            }
 
-            // Sleep before trying again, after the first iteration
-            if iterations > 1 {
-                if let Some(sleep_between_retries) = base_policy.sleep_between_retries() {
-                    thread::sleep(sleep_between_retries);
+                // Sleep before trying again, after the first iteration
+                if iterations > 1 {
+                    if let Some(sleep_between_retries) = base_policy.sleep_between_retries() {
+                        thread::sleep(sleep_between_retries);
+                    }
                 }
             }
 
             // check for command timeout
             if let Some(deadline) = deadline {
                 if Instant::now() > deadline {
-                    break;
+                    iterations -= 1;
+                    break ErrorKind::Timeout(format!(
+                        "Command timed out after {} tries",
+                        iterations
+                    ));
                 }
             }
 
             // set command node, so when you return a record it has the node
             let node = match self.get_node() {
                 Ok(node) => node,
-                Err(_) => continue, // Node is currently inactive. Retry.
+                Err(err) => {
+                    // Node is currently inactive. Retry.
+                    if !retry(&err, None, iterations, false) {
+                        return Err(self.command_error(err, iterations));
+                    }
+                    last_err = Some(err);
+                    continue;
+                }
             };
+            let attempt_span = span.attempt(iterations, &node);
+            let _attempt = attempt_span.enter();
+
+            // skip the node while it is over its error rate
+            if let Err(err) = node.validate_error_rate() {
+                if !retry(&err, Some(&node), iterations, false) {
+                    return Err(self.command_error(err, iterations));
+                }
+                last_err = Some(err);
+                continue;
+            }
 
-            let mut conn = match node.get_connection(base_policy.timeout()) {
+            let (socket_timeout, server_timeout) =
+                commands::attempt_timeouts(base_policy, deadline);
+            let mut conn = match node.get_connection(socket_timeout) {
                 Ok(conn) => conn,
                 Err(err) => {
                     warn!("Node {}: {}", node, err);
+                    commands::add_node_error(&node, &err);
+                    if !retry(&err, Some(&node), iterations, false) {
+                        return Err(self.command_error(err, iterations));
+                    }
+                    last_err = Some(err);
                     continue;
                 }
             };
 
-            self.prepare_buffer(&mut conn)
-                .chain_err(|| "Failed to prepare send buffer")?;
-            self.write_timeout(&mut conn, base_policy.timeout())
-                .chain_err(|| "Failed to set timeout for send buffer")?;
+            conn.buffer.filter_override = filter.cloned();
+            let prepared = self
+                .prepare_buffer(&mut conn)
+                .chain_err(|| "Failed to prepare send buffer")
+                .and_then(|()| {
+                    self.write_timeout(&mut conn, server_timeout)
+                        .chain_err(|| "Failed to set timeout for send buffer")
+                });
+            // never leave the override on the pooled connection for the next command
+            conn.buffer.filter_override = None;
+            if let Err(err) = prepared {
+                return Err(self.command_error(err, iterations));
+            }
 
             // Send command.
             if let Err(err) = self.write_buffer(&mut conn) {
@@ -112,6 +202,11 @@ impl<'a, 'b> BatchReadCommand<'a, 'b> {
                 // Close socket to flush out possible garbage. Do not put back in pool.
                 conn.invalidate();
                 warn!("Node {}: {}", node, err);
+                commands::add_node_error(&node, &err);
+                if !retry(&err, Some(&node), iterations, true) {
+                    return Err(self.command_error(err, iterations));
+                }
+                last_err = Some(err);
                 continue;
             }
 
@@ -124,14 +219,34 @@ impl<'a, 'b> BatchReadCommand<'a, 'b> {
                 if !commands::keep_connection(&err) {
                     conn.invalidate();
                 }
-                return Err(err);
+                commands::add_node_error(&node, &err);
+
+                // Socket timeouts are retried on another attempt; other errors only if a retry
+                // policy asks for it.
+                if (retry_policy.is_some() || commands::is_socket_timeout(&err))
+                    && retry(&err, Some(&node), iterations, true)
+                {
+                    warn!("Node {}: {}", node, err);
+                    last_err = Some(err);
+                    continue;
+                }
+                return Err(self.command_error(err, iterations));
             }
 
             // command has completed successfully.  Exit method.
             return Ok(());
-        }
+        };
 
-        bail!(ErrorKind::Connection("Timeout".to_string()))
+        let err = match last_err {
+            Some(last_err) => Error::with_chain(last_err, err),
+            None => err.into(),
+        };
+        Err(self.command_error(err, iterations))
+    }
+
+    // Attaches the context of the failed command to the error. Batch reads are never in doubt.
+    fn command_error(&self, err: Error, iterations: usize) -> Error {
+        err.with_context(Some(self.node.name().to_owned()), iterations, false)
     }
 
     fn parse_group(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
@@ -213,6 +328,26 @@ impl<'a, 'b> commands::Command for BatchReadCommand<'a, 'b> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Batch
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.node.client_policy()
+    }
+
+    fn keys(&self) -> Vec<&Key> {
+        self.offsets
+            .iter()
+            .filter_map(|idx| self.batch_reads.get(*idx))
+            .map(|batch_read| &batch_read.key)
+            .collect()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_batch_read(
             self.policy,
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::str;
 use std::time::Duration;
 
@@ -19,6 +20,7 @@ use byteorder::{ByteOrder, LittleEndian, NetworkEndian};
 
 use crate::batch::batch_executor::SharedSlice;
 use crate::commands::field_type::FieldType;
+use crate::commands::ParticleType;
 use crate::errors::Result;
 use crate::expressions::FilterExpression;
 use crate::msgpack::encoder;
@@ -102,6 +104,12 @@ pub struct Buffer {
     pub data_buffer: Vec<u8>,
     pub data_offset: usize,
     pub reclaim_threshold: usize,
+    // Send boolean values as native boolean particles instead of integers.
+    pub supports_bool: bool,
+    // Filter expression replacing the one of the policy, set by an interceptor. It is only set
+    // while the command is written to the buffer, and cleared afterwards even if the command
+    // does not use it.
+    pub filter_override: Option<FilterExpression>,
 }
 
 impl Buffer {
@@ -110,6 +118,8 @@ impl Buffer {
             data_buffer: Vec::with_capacity(1024),
             data_offset: 0,
             reclaim_threshold,
+            supports_bool: false,
+            filter_override: None,
         }
     }
 
@@ -167,7 +177,8 @@ impl Buffer {
     ) -> Result<()> {
         self.begin()?;
         let mut field_count = self.estimate_key_size(key, policy.send_key)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -186,7 +197,7 @@ impl Buffer {
         )?;
         self.write_key(key, policy.send_key)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
         for bin in bins {
@@ -200,7 +211,8 @@ impl Buffer {
     pub fn set_delete(&mut self, policy: &WritePolicy, key: &Key) -> Result<()> {
         self.begin()?;
         let mut field_count = self.estimate_key_size(key, false)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -215,7 +227,7 @@ impl Buffer {
         )?;
         self.write_key(key, false)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -226,7 +238,8 @@ impl Buffer {
     pub fn set_touch(&mut self, policy: &WritePolicy, key: &Key) -> Result<()> {
         self.begin()?;
         let mut field_count = self.estimate_key_size(key, policy.send_key)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -235,7 +248,7 @@ impl Buffer {
         self.write_header_with_policy(policy, 0, INFO2_WRITE, field_count as u16, 1)?;
         self.write_key(key, policy.send_key)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -247,7 +260,8 @@ impl Buffer {
     pub fn set_exists(&mut self, policy: &WritePolicy, key: &Key) -> Result<()> {
         self.begin()?;
         let mut field_count = self.estimate_key_size(key, false)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -262,7 +276,7 @@ impl Buffer {
         )?;
         self.write_key(key, false)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -277,7 +291,8 @@ impl Buffer {
             Bins::Some(ref bin_names) => {
                 self.begin()?;
                 let mut field_count = self.estimate_key_size(key, false)?;
-                let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+                let filter = self.take_filter(policy.filter_expression().as_ref());
+                let filter_size = self.estimate_filter_size(filter.as_deref())?;
                 if filter_size > 0 {
                     field_count += 1;
                 }
@@ -289,7 +304,7 @@ impl Buffer {
                 self.write_header(policy, INFO1_READ, 0, field_count, bin_names.len() as u16)?;
                 self.write_key(key, false)?;
 
-                if let Some(filter) = policy.filter_expression() {
+                if let Some(filter) = filter.as_deref() {
                     self.write_filter_expression(filter, filter_size)?;
                 }
 
@@ -307,7 +322,8 @@ impl Buffer {
     pub fn set_read_header(&mut self, policy: &ReadPolicy, key: &Key) -> Result<()> {
         self.begin()?;
         let mut field_count = self.estimate_key_size(key, false)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -317,7 +333,7 @@ impl Buffer {
         self.write_header(policy, INFO1_READ | INFO1_NOBINDATA, 0, field_count, 1)?;
         self.write_key(key, false)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -329,7 +345,8 @@ impl Buffer {
         self.begin()?;
 
         let mut field_count = self.estimate_key_size(key, false)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -338,7 +355,7 @@ impl Buffer {
         self.write_header(policy, INFO1_READ | INFO1_GET_ALL, 0, field_count, 0)?;
         self.write_key(key, false)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -358,7 +375,9 @@ impl Buffer {
         let mut field_count = 1;
         self.data_offset += FIELD_HEADER_SIZE as usize + 5;
 
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -396,7 +415,7 @@ impl Buffer {
             0,
         )?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -527,11 +546,12 @@ impl Buffer {
                 write_attr |= INFO2_RESPOND_ALL_OPS;
             }
 
-            self.data_offset += operation.estimate_size()? + OPERATION_HEADER_SIZE as usize;
+            self.data_offset += operation.estimate_size(self)? + OPERATION_HEADER_SIZE as usize;
         }
 
         let mut field_count = self.estimate_key_size(key, policy.send_key && write_attr != 0)?;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -556,7 +576,7 @@ impl Buffer {
         }
         self.write_key(key, policy.send_key && write_attr != 0)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -578,7 +598,8 @@ impl Buffer {
 
         let mut field_count = self.estimate_key_size(key, policy.send_key)?;
         field_count += self.estimate_udf_size(package_name, function_name, args)? as u16;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -587,7 +608,7 @@ impl Buffer {
         self.write_header(&policy.base_policy, 0, INFO2_WRITE, field_count, 0)?;
         self.write_key(key, policy.send_key)?;
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -609,7 +630,8 @@ impl Buffer {
         self.begin()?;
 
         let mut field_count = 0;
-        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter = self.take_filter(policy.filter_expression().as_ref());
+        let filter_size = self.estimate_filter_size(filter.as_deref())?;
         if filter_size > 0 {
             field_count += 1;
         }
@@ -678,7 +700,7 @@ impl Buffer {
             self.write_u16_little_endian(*pid)?;
         }
 
-        if let Some(filter) = policy.filter_expression() {
+        if let Some(filter) = filter.as_deref() {
             self.write_filter_expression(filter, filter_size)?;
         }
 
@@ -694,9 +716,14 @@ impl Buffer {
         // self.write_u8(priority)?;
         // self.write_u8(policy.scan_percent)?;
 
-        // Write scan timeout
+        // Write scan socket timeout
+        #[allow(deprecated)]
+        let socket_timeout = match policy.base_policy.socket_timeout {
+            Some(timeout) => timeout.as_millis().min(u128::from(u32::MAX)) as u32,
+            None => policy.socket_timeout,
+        };
         self.write_field_header(4, FieldType::ScanTimeout)?;
-        self.write_u32(policy.socket_timeout)?;
+        self.write_u32(socket_timeout)?;
 
         self.write_field_header(8, FieldType::TranId)?;
         self.write_u64(task_id)?;
@@ -782,7 +809,8 @@ impl Buffer {
             self.data_offset += partitions.len() * 2 + FIELD_HEADER_SIZE as usize;
             field_count += 1;
         }
-        let filter_exp_size = self.estimate_filter_size(policy.filter_expression())?;
+        let filter_exp = self.take_filter(policy.filter_expression().as_ref());
+        let filter_exp_size = self.estimate_filter_size(filter_exp.as_deref())?;
         if filter_exp_size > 0 {
             field_count += 1;
         }
@@ -884,7 +912,7 @@ impl Buffer {
             }
         }
 
-        if let Some(filter_exp) = policy.filter_expression() {
+        if let Some(filter_exp) = filter_exp.as_deref() {
             self.write_filter_expression(filter_exp, filter_exp_size)?;
         }
 
@@ -917,7 +945,19 @@ impl Buffer {
         self.end()
     }
 
-    fn estimate_filter_size(&mut self, filter: &Option<FilterExpression>) -> Result<usize> {
+    // Returns the filter expression to send with the command: the override set by an
+    // interceptor, if any, or the filter expression of the policy.
+    fn take_filter<'p>(
+        &mut self,
+        policy_filter: Option<&'p FilterExpression>,
+    ) -> Option<Cow<'p, FilterExpression>> {
+        match self.filter_override.take() {
+            Some(filter) => Some(Cow::Owned(filter)),
+            None => policy_filter.map(Cow::Borrowed),
+        }
+    }
+
+    fn estimate_filter_size(&mut self, filter: Option<&FilterExpression>) -> Result<usize> {
         if let Some(filter) = filter {
             let filter_size = filter.pack(&mut None)?;
             self.data_offset += filter_size + FIELD_HEADER_SIZE as usize;
@@ -978,7 +1018,7 @@ impl Buffer {
 
     fn estimate_operation_size_for_bin(&mut self, bin: &Bin) -> Result<()> {
         self.data_offset += bin.name.len() + OPERATION_HEADER_SIZE as usize;
-        self.data_offset += bin.value.estimate_size()?;
+        self.data_offset += self.estimate_value_size(&bin.value)?;
         Ok(())
     }
 
@@ -1167,19 +1207,42 @@ impl Buffer {
 
     fn write_operation_for_bin(&mut self, bin: &Bin, op_type: OperationType) -> Result<()> {
         let name_length = bin.name.len();
-        let value_length = bin.value.estimate_size()?;
+        let value_length = self.estimate_value_size(&bin.value)?;
 
         self.write_i32((name_length + value_length + 4) as i32)?;
         self.write_u8(op_type as u8)?;
-        self.write_u8(bin.value.particle_type() as u8)?;
+        self.write_u8(self.value_particle_type(&bin.value) as u8)?;
         self.write_u8(0)?;
         self.write_u8(name_length as u8)?;
         self.write_str(bin.name)?;
-        bin.value.write_to(self)?;
+        self.write_value(&bin.value)?;
 
         Ok(())
     }
 
+    // Particle type used to send a bin value. Booleans are sent as integers to servers that do not
+    // support native boolean particles.
+    pub fn value_particle_type(&self, value: &Value) -> ParticleType {
+        match *value {
+            Value::Bool(_) if self.supports_bool => ParticleType::BOOL,
+            _ => value.particle_type(),
+        }
+    }
+
+    pub fn estimate_value_size(&self, value: &Value) -> Result<usize> {
+        match *value {
+            Value::Bool(_) if self.supports_bool => Ok(1),
+            _ => value.estimate_size(),
+        }
+    }
+
+    pub fn write_value(&mut self, value: &Value) -> Result<usize> {
+        match *value {
+            Value::Bool(val) if self.supports_bool => self.write_u8(u8::from(val)),
+            _ => value.write_to(self),
+        }
+    }
+
     fn write_operation_for_bin_name(&mut self, name: &str, op_type: OperationType) -> Result<()> {
         self.write_i32(name.len() as i32 + 4)?;
         self.write_u8(op_type as u8)?;
@@ -18,7 +18,10 @@ use std::time::Duration;
 use crate::cluster::{Cluster, Node};
 use crate::commands::{buffer, Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
+use crate::policy::ClientPolicy;
 use crate::policy::WritePolicy;
 use crate::{Key, ResultCode};
 
@@ -52,6 +55,22 @@ impl<'a> Command for DeleteCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Write
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.single_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
+    fn key(&self) -> Option<&Key> {
+        Some(self.single_command.key)
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_delete(self.policy, self.single_command.key)
     }
@@ -60,6 +79,10 @@ impl<'a> Command for DeleteCommand<'a> {
         self.single_command.get_node()
     }
 
+    fn is_write(&self) -> bool {
+        true
+    }
+
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         // Read header.
         if let Err(err) = conn.read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize) {
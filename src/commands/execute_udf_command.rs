@@ -19,7 +19,10 @@ use std::time::Duration;
 use crate::cluster::{Cluster, Node};
 use crate::commands::{Command, ReadCommand, SingleCommand};
 use crate::errors::Result;
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
+use crate::policy::ClientPolicy;
 use crate::policy::WritePolicy;
 use crate::{Bins, Key, Value};
 
@@ -64,6 +67,22 @@ impl<'a> Command for ExecuteUDFCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Udf
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.read_command.single_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
+    fn key(&self) -> Option<&Key> {
+        Some(self.read_command.single_command.key)
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_udf(
             self.policy,
@@ -78,6 +97,10 @@ impl<'a> Command for ExecuteUDFCommand<'a> {
         self.read_command.get_node()
     }
 
+    fn is_write(&self) -> bool {
+        true
+    }
+
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         self.read_command.parse_result(conn)
     }
@@ -26,13 +26,17 @@ pub mod read_command;
 pub mod scan_command;
 pub mod single_command;
 pub mod stream_command;
+#[cfg(test)]
+pub mod test_util;
 pub mod touch_command;
 pub mod write_command;
 
-mod field_type;
+pub mod field_type;
 
+use std::io;
 use std::sync::Arc;
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub use self::batch_read_command::BatchReadCommand;
 pub use self::delete_command::DeleteCommand;
@@ -51,8 +55,11 @@ pub use self::write_command::WriteCommand;
 
 use crate::cluster::Node;
 use crate::errors::{Error, ErrorKind, Result};
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
-use crate::ResultCode;
+use crate::policy::{ClientPolicy, Policy, RetryContext, RetryPolicy};
+use crate::{Key, ResultCode};
 
 // Command interface describes all commands available
 pub trait Command {
@@ -61,6 +68,40 @@ pub trait Command {
     fn get_node(&self) -> Result<Arc<Node>>;
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()>;
     fn write_buffer(&mut self, conn: &mut Connection) -> Result<()>;
+    fn command_type(&self) -> CommandType;
+    fn client_policy(&self) -> &ClientPolicy;
+
+    // Whether the command modifies the record, i.e. a failed attempt might have been applied.
+    fn is_write(&self) -> bool {
+        false
+    }
+
+    // Whether the command can be retried after parsing its response failed, i.e. no partial
+    // results have been handed out to the caller.
+    fn can_retry_response(&self) -> bool {
+        true
+    }
+
+    // Key of the record the command operates on, if it is a single record command.
+    fn key(&self) -> Option<&Key> {
+        None
+    }
+
+    // Keys of the records the command operates on.
+    fn keys(&self) -> Vec<&Key> {
+        self.key().into_iter().collect()
+    }
+
+    // Filter expression of the command's policy.
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        None
+    }
+
+    // Whether the error is caused by the caller aborting the command, in which case the command
+    // is not reported as failed to the metrics listener.
+    fn is_caller_abort(&self, _err: &Error) -> bool {
+        false
+    }
 }
 
 pub const fn keep_connection(err: &Error) -> bool {
@@ -71,3 +112,334 @@ pub const fn keep_connection(err: &Error) -> bool {
         _ => false,
     }
 }
+
+// Returns the socket timeout and the server-side timeout for the next attempt of a command. Both
+// are capped by the time remaining until the deadline of the command.
+pub fn attempt_timeouts(
+    policy: &dyn Policy,
+    deadline: Option<Instant>,
+) -> (Option<Duration>, Option<Duration>) {
+    // a zero timeout is rejected by the socket and means "no timeout" to the server
+    let min_timeout = Duration::from_millis(1);
+    let remaining = deadline.map(|deadline| {
+        deadline
+            .saturating_duration_since(Instant::now())
+            .max(min_timeout)
+    });
+    let socket_timeout = policy
+        .socket_timeout()
+        .filter(|timeout| *timeout >= min_timeout);
+    match (socket_timeout, remaining) {
+        (Some(socket_timeout), Some(remaining)) => {
+            (Some(socket_timeout.min(remaining)), Some(remaining))
+        }
+        (socket_timeout, remaining) => (socket_timeout.or(remaining), remaining.or(socket_timeout)),
+    }
+}
+
+// Decides whether a failed attempt is retried. Without a retry policy, every failed attempt is
+// retried and the retries are bounded by `max_retries` at the start of every iteration. With a
+// retry policy, `max_retries` still caps the number of retries; otherwise sleeps for the delay
+// the retry policy returns, capped by the time remaining until the deadline.
+pub fn retry_attempt(
+    retry_policy: Option<&dyn RetryPolicy>,
+    max_retries: Option<usize>,
+    deadline: Option<Instant>,
+    ctx: &RetryContext,
+) -> bool {
+    let retry_policy = match retry_policy {
+        Some(retry_policy) => retry_policy,
+        None => return true,
+    };
+    if max_retries.map_or(false, |max_retries| ctx.attempt > max_retries) {
+        return false;
+    }
+    let mut delay = match retry_policy.retry_delay(ctx) {
+        Some(delay) => delay,
+        None => return false,
+    };
+    if let Some(deadline) = deadline {
+        delay = delay.min(deadline.saturating_duration_since(Instant::now()));
+    }
+    if delay > Duration::from_secs(0) {
+        thread::sleep(delay);
+    }
+    true
+}
+
+// Whether the error is caused by a socket read or write timing out.
+pub fn is_socket_timeout(err: &Error) -> bool {
+    match *err.kind() {
+        ErrorKind::Io(ref err) => matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        ),
+        _ => false,
+    }
+}
+
+// Counts errors that indicate an unhealthy node, i.e. network errors, socket timeouts and device
+// overload, towards the error rate of the node. Errors caused by contention on a record or by
+// client-side limits are not counted.
+pub fn add_node_error(node: &Node, err: &Error) {
+    if is_node_error(err) {
+        node.add_error();
+    }
+}
+
+fn is_node_error(err: &Error) -> bool {
+    matches!(
+        *err.kind(),
+        ErrorKind::Connection(_)
+            | ErrorKind::Io(_)
+            | ErrorKind::ServerError(ResultCode::DeviceOverload)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::test_util::{self, InfoTransport, RefusingTransport, StubCommand};
+    use super::*;
+    use crate::expressions::{eq, int_bin, int_val};
+    use crate::interceptor::{CommandContext, Interceptor};
+    use crate::policy::{BasePolicy, ConsistencyLevel, ExponentialBackoff, Priority, ScanPolicy};
+    use crate::Bins;
+
+    #[test]
+    fn attempt_timeouts() {
+        let mut policy = BasePolicy {
+            timeout: None,
+            socket_timeout: None,
+            ..BasePolicy::default()
+        };
+        assert_eq!(super::attempt_timeouts(&policy, None), (None, None));
+
+        policy.socket_timeout = Some(Duration::from_secs(1));
+        assert_eq!(
+            super::attempt_timeouts(&policy, None),
+            (Some(Duration::from_secs(1)), Some(Duration::from_secs(1)))
+        );
+
+        // the socket timeout is capped by the remaining time, which is sent to the server
+        let deadline = Instant::now() + Duration::from_millis(500);
+        let (socket_timeout, server_timeout) = super::attempt_timeouts(&policy, Some(deadline));
+        assert!(socket_timeout.unwrap() <= Duration::from_millis(500));
+        assert_eq!(socket_timeout, server_timeout);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let (socket_timeout, server_timeout) = super::attempt_timeouts(&policy, Some(deadline));
+        assert_eq!(socket_timeout, Some(Duration::from_secs(1)));
+        assert!(server_timeout.unwrap() > Duration::from_secs(9));
+
+        // an expired deadline still yields a valid socket timeout
+        let (socket_timeout, _) = super::attempt_timeouts(&policy, Some(Instant::now()));
+        assert_eq!(socket_timeout, Some(Duration::from_millis(1)));
+    }
+
+    // Implements only the methods `Policy` required before socket timeouts and retry policies.
+    struct CustomPolicy {
+        timeout: Option<Duration>,
+    }
+
+    impl Policy for CustomPolicy {
+        fn priority(&self) -> &Priority {
+            &Priority::Default
+        }
+
+        fn deadline(&self) -> Option<Instant> {
+            self.timeout.map(|timeout| Instant::now() + timeout)
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            self.timeout
+        }
+
+        fn max_retries(&self) -> Option<usize> {
+            None
+        }
+
+        fn sleep_between_retries(&self) -> Option<Duration> {
+            None
+        }
+
+        fn consistency_level(&self) -> &ConsistencyLevel {
+            &ConsistencyLevel::ConsistencyOne
+        }
+    }
+
+    #[test]
+    fn custom_policy() {
+        let policy = CustomPolicy {
+            timeout: Some(Duration::from_secs(1)),
+        };
+        assert_eq!(policy.total_timeout(), Some(Duration::from_secs(1)));
+        assert!(policy.retry_policy().is_none());
+
+        // without a socket timeout, the remaining time is used as the socket timeout
+        let (socket_timeout, server_timeout) = super::attempt_timeouts(&policy, policy.deadline());
+        assert!(socket_timeout.unwrap() <= Duration::from_secs(1));
+        assert_eq!(socket_timeout, server_timeout);
+    }
+
+    #[test]
+    fn retry_policy_max_retries() {
+        let transport = Arc::new(RefusingTransport::default());
+        let node = test_util::node(test_util::refusing_policy(transport.clone()));
+        let mut cmd = StubCommand {
+            node: node.clone(),
+            key: None,
+        };
+        let backoff = ExponentialBackoff {
+            max_retries: 5,
+            initial_delay: Duration::from_millis(1),
+            ..ExponentialBackoff::default()
+        };
+
+        // Without a deadline, `max_retries` caps the retries allowed by the retry policy.
+        let mut policy = BasePolicy {
+            timeout: None,
+            max_retries: Some(1),
+            retry_policy: Some(Arc::new(backoff)),
+            ..BasePolicy::default()
+        };
+        // The error of the last attempt is returned.
+        let err = SingleCommand::execute(&policy, &mut cmd).unwrap_err();
+        assert!(
+            matches!(err.kind(), ErrorKind::Io(err) if err.kind() == io::ErrorKind::ConnectionRefused)
+        );
+        assert_eq!(err.iterations(), 2);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 2);
+
+        // Without `max_retries`, the retry policy alone decides.
+        policy.max_retries = None;
+        let err = SingleCommand::execute(&policy, &mut cmd).unwrap_err();
+        assert_eq!(err.iterations(), 6);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 8);
+    }
+
+    #[derive(Debug)]
+    struct FilterInterceptor;
+
+    impl Interceptor for FilterInterceptor {
+        fn before_command(&self, ctx: &mut CommandContext) -> Result<()> {
+            ctx.set_filter_expression(eq(int_bin("tenant".to_string()), int_val(42)));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn filter_override() {
+        let mut client_policy = InfoTransport::new(|_| String::new()).policy();
+        client_policy.interceptors.push(Arc::new(FilterInterceptor));
+        let node = test_util::node(client_policy);
+        let mut cmd = StubCommand {
+            node: node.clone(),
+            key: None,
+        };
+
+        // The command does not write the override to the buffer; it must not be left on the
+        // pooled connection for the next command.
+        SingleCommand::execute(&BasePolicy::default(), &mut cmd).unwrap();
+        assert_eq!(node.connection_count(), 1);
+        let conn = node.get_connection(None).unwrap();
+        assert!(conn.buffer.filter_override.is_none());
+    }
+
+    #[test]
+    fn node_errors() {
+        let timeout: Error = io::Error::from(io::ErrorKind::TimedOut).into();
+        assert!(is_node_error(&timeout));
+        let err: Error = ErrorKind::Connection("reset".into()).into();
+        assert!(is_node_error(&err));
+        let err: Error = ErrorKind::ServerError(ResultCode::DeviceOverload).into();
+        assert!(is_node_error(&err));
+
+        for rc in &[ResultCode::KeyBusy, ResultCode::QueryQueueFull] {
+            let err: Error = ErrorKind::ServerError(*rc).into();
+            assert!(!is_node_error(&err));
+        }
+        let err: Error = ErrorKind::NoMoreConnections.into();
+        assert!(!is_node_error(&err));
+        let err: Error = ErrorKind::MaxErrorRate("BB9".into()).into();
+        assert!(!is_node_error(&err));
+    }
+
+    #[test]
+    fn max_error_rate() {
+        // error rate limiting is disabled by default
+        let transport = Arc::new(RefusingTransport::default());
+        let node = test_util::node(test_util::refusing_policy(transport.clone()));
+        for _ in 0..1000 {
+            node.add_error();
+        }
+        assert!(node.validate_error_rate().is_ok());
+        assert_eq!(node.error_rate_trips(), 0);
+
+        let transport = Arc::new(RefusingTransport::default());
+        let client_policy = ClientPolicy {
+            max_error_rate: 2,
+            ..test_util::refusing_policy(transport.clone())
+        };
+        let node = test_util::node(client_policy.clone());
+        let policy = BasePolicy {
+            max_retries: Some(5),
+            sleep_between_retries: None,
+            ..BasePolicy::default()
+        };
+        let mut cmd = StubCommand {
+            node: node.clone(),
+            key: None,
+        };
+
+        // The third failed connection exceeds the limit; the remaining attempts skip the node
+        // without connecting until the retries are exhausted.
+        let err = SingleCommand::execute(&policy, &mut cmd).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Connection(_)));
+        assert!(err
+            .iter()
+            .any(|cause| cause.to_string() == "Max. error rate exceeded for node BB9"));
+        assert_eq!(err.iterations(), 6);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 3);
+        assert_eq!(node.error_count(), 3);
+        assert_eq!(node.error_rate_trips(), 1);
+
+        // Commands skip the node until the error rate window resets.
+        let err = SingleCommand::execute(&policy, &mut cmd).unwrap_err();
+        assert_eq!(err.iterations(), 6);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 3);
+
+        // A retry policy decides whether a rejected attempt is retried.
+        let backoff_policy = BasePolicy {
+            retry_policy: Some(Arc::new(ExponentialBackoff {
+                max_retries: 1,
+                initial_delay: Duration::from_millis(1),
+                ..ExponentialBackoff::default()
+            })),
+            ..policy.clone()
+        };
+        let err = SingleCommand::execute(&backoff_policy, &mut cmd).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::MaxErrorRate(node) if node == "BB9"));
+        assert_eq!(err.iterations(), 2);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 3);
+
+        // Scans pass the rejection on through the recordset.
+        let client = test_util::client(client_policy, vec![node.clone()]);
+        let mut scan_policy = ScanPolicy::default();
+        scan_policy.base_policy.sleep_between_retries = None;
+        let recordset = client
+            .scan(&scan_policy, "test", "test", Bins::All)
+            .unwrap();
+        let results: Vec<_> = recordset.as_ref().into_iter().collect();
+        assert_eq!(results.len(), 1);
+        let err = results.into_iter().next().unwrap().unwrap_err();
+        assert!(err
+            .iter()
+            .any(|cause| cause.to_string() == "Max. error rate exceeded for node BB9"));
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 3);
+
+        node.reset_error_count();
+        assert!(node.validate_error_rate().is_ok());
+    }
+}
@@ -18,8 +18,11 @@ use std::time::Duration;
 use crate::cluster::{Cluster, Node};
 use crate::commands::{Command, ReadCommand, SingleCommand};
 use crate::errors::Result;
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
-use crate::operations::Operation;
+use crate::operations::{Operation, OperationType};
+use crate::policy::ClientPolicy;
 use crate::policy::WritePolicy;
 use crate::{Bins, Key};
 
@@ -58,6 +61,22 @@ impl<'a> Command for OperateCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Operate
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.read_command.single_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
+    fn key(&self) -> Option<&Key> {
+        Some(self.read_command.single_command.key)
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_operate(
             self.policy,
@@ -73,4 +92,17 @@ impl<'a> Command for OperateCommand<'a> {
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         self.read_command.parse_result(conn)
     }
+
+    fn is_write(&self) -> bool {
+        self.operations.iter().any(|op| {
+            !matches!(
+                op.op,
+                OperationType::Read
+                    | OperationType::CdtRead
+                    | OperationType::BitRead
+                    | OperationType::HllRead
+                    | OperationType::ExpRead
+            )
+        })
+    }
 }
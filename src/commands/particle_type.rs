@@ -13,6 +13,10 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::convert::TryFrom;
+
+use crate::errors::{Error, ErrorKind, Result};
+
 #[derive(Debug, Clone)]
 #[doc(hidden)]
 pub enum ParticleType {
@@ -34,7 +38,7 @@ pub enum ParticleType {
     // RTA_LIST        = 14,
     // RTA_DICT        = 15,
     // RTA_APPEND_DICT = 16,
-    // RTA_APPEND_LIST = 17,
+    BOOL = 17,
     // LUA_BLOB        = 18,
     HLL = 18,
     MAP = 19,
@@ -43,9 +47,11 @@ pub enum ParticleType {
     GEOJSON = 23,
 }
 
-impl From<u8> for ParticleType {
-    fn from(val: u8) -> ParticleType {
-        match val {
+impl TryFrom<u8> for ParticleType {
+    type Error = Error;
+
+    fn try_from(val: u8) -> Result<ParticleType> {
+        let ptype = match val {
             0 => ParticleType::NULL,
             1 => ParticleType::INTEGER,
             2 => ParticleType::FLOAT,
@@ -63,14 +69,31 @@ impl From<u8> for ParticleType {
             // 14 => ParticleType::RTA_LIST       ,
             // 15 => ParticleType::RTA_DICT       ,
             // 16 => ParticleType::RTA_APPEND_DICT,
-            // 17 => ParticleType::RTA_APPEND_LIST,
+            17 => ParticleType::BOOL,
             // 18 => ParticleType::LUA_BLOB       ,
             18 => ParticleType::HLL,
             19 => ParticleType::MAP,
             20 => ParticleType::LIST,
             21 => ParticleType::LDT,
             23 => ParticleType::GEOJSON,
-            _ => unreachable!(),
-        }
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Unknown particle type {}",
+                val
+            ))),
+        };
+        Ok(ptype)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParticleType;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn try_from_u8() {
+        assert!(matches!(ParticleType::try_from(17), Ok(ParticleType::BOOL)));
+        assert!(ParticleType::try_from(5).is_err());
+        assert!(ParticleType::try_from(255).is_err());
     }
 }
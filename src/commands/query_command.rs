@@ -16,14 +16,18 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::cluster::Node;
+use crate::commands::stream_command::RecordHandler;
 use crate::commands::{Command, SingleCommand, StreamCommand};
-use crate::errors::Result;
+use crate::errors::{Error, Result};
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
+use crate::policy::ClientPolicy;
 use crate::policy::QueryPolicy;
 use crate::{Recordset, Statement};
 
 pub struct QueryCommand<'a> {
-    stream_command: StreamCommand,
+    stream_command: StreamCommand<'a>,
     policy: &'a QueryPolicy,
     statement: Arc<Statement>,
     partitions: Vec<u16>,
@@ -36,9 +40,10 @@ impl<'a> QueryCommand<'a> {
         statement: Arc<Statement>,
         recordset: Arc<Recordset>,
         partitions: Vec<u16>,
+        handler: RecordHandler<'a>,
     ) -> Self {
         QueryCommand {
-            stream_command: StreamCommand::new(node, recordset),
+            stream_command: StreamCommand::new(node, &recordset, handler),
             policy,
             statement,
             partitions,
@@ -46,7 +51,12 @@ impl<'a> QueryCommand<'a> {
     }
 
     pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+        let result = SingleCommand::execute(self.policy, self);
+        self.stream_command.finish(result)
+    }
+
+    pub fn push_error(&self, err: Error) {
+        self.stream_command.push_error(err);
     }
 }
 
@@ -60,12 +70,24 @@ impl<'a> Command for QueryCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Query
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.stream_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_query(
             self.policy,
             &self.statement,
             false,
-            self.stream_command.recordset.task_id(),
+            self.stream_command.task_id(),
             &self.partitions,
         )
     }
@@ -77,4 +99,12 @@ impl<'a> Command for QueryCommand<'a> {
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         StreamCommand::parse_result(&mut self.stream_command, conn)
     }
+
+    fn can_retry_response(&self) -> bool {
+        false
+    }
+
+    fn is_caller_abort(&self, err: &Error) -> bool {
+        self.stream_command.is_caller_abort(err)
+    }
 }
@@ -21,7 +21,10 @@ use crate::cluster::{Cluster, Node};
 use crate::commands::buffer;
 use crate::commands::{Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
+use crate::policy::ClientPolicy;
 use crate::policy::ReadPolicy;
 use crate::value::bytes_to_particle;
 use crate::{Bins, Key, Record, ResultCode, Value};
@@ -105,6 +108,22 @@ impl<'a> Command for ReadCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Read
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.single_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
+    fn key(&self) -> Option<&Key> {
+        Some(self.single_command.key)
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer
             .set_read(self.policy, self.single_command.key, &self.bins)
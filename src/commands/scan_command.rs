@@ -17,14 +17,18 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::cluster::Node;
+use crate::commands::stream_command::RecordHandler;
 use crate::commands::{Command, SingleCommand, StreamCommand};
-use crate::errors::Result;
+use crate::errors::{Error, Result};
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
+use crate::policy::ClientPolicy;
 use crate::policy::ScanPolicy;
 use crate::{Bins, Recordset};
 
 pub struct ScanCommand<'a> {
-    stream_command: StreamCommand,
+    stream_command: StreamCommand<'a>,
     policy: &'a ScanPolicy,
     namespace: &'a str,
     set_name: &'a str,
@@ -33,6 +37,7 @@ pub struct ScanCommand<'a> {
 }
 
 impl<'a> ScanCommand<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         policy: &'a ScanPolicy,
         node: Arc<Node>,
@@ -41,9 +46,10 @@ impl<'a> ScanCommand<'a> {
         bins: Bins,
         recordset: Arc<Recordset>,
         partitions: Vec<u16>,
+        handler: RecordHandler<'a>,
     ) -> Self {
         ScanCommand {
-            stream_command: StreamCommand::new(node, recordset),
+            stream_command: StreamCommand::new(node, &recordset, handler),
             policy,
             namespace,
             set_name,
@@ -53,7 +59,12 @@ impl<'a> ScanCommand<'a> {
     }
 
     pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+        let result = SingleCommand::execute(self.policy, self);
+        self.stream_command.finish(result)
+    }
+
+    pub fn push_error(&self, err: Error) {
+        self.stream_command.push_error(err);
     }
 }
 
@@ -67,13 +78,25 @@ impl<'a> Command for ScanCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Scan
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.stream_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_scan(
             self.policy,
             self.namespace,
             self.set_name,
             &self.bins,
-            self.stream_command.recordset.task_id(),
+            self.stream_command.task_id(),
             &self.partitions,
         )
     }
@@ -85,4 +108,12 @@ impl<'a> Command for ScanCommand<'a> {
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         StreamCommand::parse_result(&mut self.stream_command, conn)
     }
+
+    fn can_retry_response(&self) -> bool {
+        false
+    }
+
+    fn is_caller_abort(&self, err: &Error) -> bool {
+        self.stream_command.is_caller_abort(err)
+    }
 }
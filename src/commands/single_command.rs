@@ -19,10 +19,14 @@ use std::time::Instant;
 use crate::cluster::partition::Partition;
 use crate::cluster::{Cluster, Node};
 use crate::commands::{self};
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::expressions::FilterExpression;
+use crate::interceptor;
+use crate::metrics;
 use crate::net::Connection;
-use crate::policy::Policy;
-use crate::Key;
+use crate::policy::{ClientPolicy, Policy, RetryContext};
+use crate::trace;
+use crate::{Key, ResultCode};
 
 pub struct SingleCommand<'a> {
     cluster: Arc<Cluster>,
@@ -44,6 +48,10 @@ impl<'a> SingleCommand<'a> {
         self.cluster.get_node(&self.partition)
     }
 
+    pub fn client_policy(&self) -> &ClientPolicy {
+        self.cluster.client_policy()
+    }
+
     pub fn empty_socket(conn: &mut Connection) -> Result<()> {
         // There should not be any more bytes.
         // Empty the socket to be safe.
@@ -64,64 +72,201 @@ impl<'a> SingleCommand<'a> {
     //
 
     pub fn execute(policy: &dyn Policy, cmd: &'a mut dyn commands::Command) -> Result<()> {
+        let start = Instant::now();
+        let command_type = cmd.command_type();
+        let span = trace::Span::command(command_type, cmd.key());
+        let _entered = span.enter();
+        let interceptors = cmd.client_policy().interceptors.clone();
+        let mut node = None;
+        let (filter, result) = match interceptor::before_command(&interceptors, policy, cmd, start)
+        {
+            Ok(filter) => {
+                let result = Self::execute_attempts(policy, cmd, filter.as_ref(), &span, &mut node);
+                (filter, result)
+            }
+            Err(err) => (None, Err(err)),
+        };
+        span.record_result(&result);
+        if let Some(ref node) = node {
+            match result {
+                Err(ref err) if cmd.is_caller_abort(err) => {
+                    metrics::record(node, command_type, start, &Ok(()));
+                }
+                _ => metrics::record(node, command_type, start, &result),
+            }
+        }
+        interceptor::after_command(
+            &interceptors,
+            policy,
+            cmd,
+            filter.as_ref(),
+            node,
+            start,
+            &result,
+        );
+        result
+    }
+
+    // Executes the command until successful, timed out or maximum iterations have been reached.
+    // The node of the last attempt is returned in `node`.
+    fn execute_attempts(
+        policy: &dyn Policy,
+        cmd: &mut dyn commands::Command,
+        filter: Option<&FilterExpression>,
+        span: &trace::Span,
+        node: &mut Option<Arc<Node>>,
+    ) -> Result<()> {
         let mut iterations = 0;
+        // number of times the command has been sent to a server, including partially
+        let mut sent = 0;
+        let mut last_err: Option<Error> = None;
+        let is_write = cmd.is_write();
 
         // set timeout outside the loop
         let deadline = policy.deadline();
+        let retry_policy = policy.retry_policy();
+        let max_retries = policy.max_retries();
+
+        let retry = |err: &Error, node: Option<&Arc<Node>>, attempt: usize, sent: bool| {
+            let ctx = RetryContext {
+                attempt,
+                error: err,
+                node,
+                is_write,
+                sent,
+            };
+            commands::retry_attempt(retry_policy, max_retries, deadline, &ctx)
+        };
 
         // Execute command until successful, timed out or maximum iterations have been reached.
-        loop {
+        let err = loop {
             iterations += 1;
 
-            // too many retries
-            if let Some(max_retries) = policy.max_retries() {
-                if iterations > max_retries + 1 {
-                    bail!(ErrorKind::Connection(format!(
-                        "Timeout after {} tries",
-                        iterations
-                    )));
+            if retry_policy.is_none() {
+                // too many retries
+                if let Some(max_retries) = max_retries {
+                    if iterations > max_retries + 1 {
+                        iterations -= 1;
+                        break ErrorKind::Connection(format!("Timeout after {} tries", iterations));
+                    }
                 }
-            }
 
-            // Sleep before trying again, after the first iteration
-            if iterations > 1 {
-                if let Some(sleep_between_retries) = policy.sleep_between_retries() {
-                    thread::sleep(sleep_between_retries);
+                // Sleep before trying again, after the first iteration
+                if iterations > 1 {
+                    if let Some(sleep_between_retries) = policy.sleep_between_retries() {
+                        thread::sleep(sleep_between_retries);
+                    }
                 }
             }
 
             // check for command timeout
             if let Some(deadline) = deadline {
                 if Instant::now() > deadline {
-                    break;
+                    iterations -= 1;
+                    break ErrorKind::Timeout(format!(
+                        "Command timed out after {} tries",
+                        iterations
+                    ));
                 }
             }
 
             // set command node, so when you return a record it has the node
-            let node = match cmd.get_node() {
+            let current = match cmd.get_node() {
                 Ok(node) => node,
-                Err(_) => continue, // Node is currently inactive. Retry.
+                Err(err) => {
+                    // Node is currently inactive. Retry.
+                    if !retry(&err, None, iterations, false) {
+                        return Err(Self::command_error(
+                            err,
+                            node.as_ref(),
+                            iterations,
+                            sent,
+                            is_write,
+                        ));
+                    }
+                    last_err = Some(err);
+                    continue;
+                }
             };
+            *node = Some(current.clone());
+            let attempt_span = span.attempt(iterations, &current);
+            let _attempt = attempt_span.enter();
 
-            let mut conn = match node.get_connection(policy.timeout()) {
+            // skip the node while it is over its error rate; the next attempt goes to the node
+            // owning the partition at that time
+            if let Err(err) = current.validate_error_rate() {
+                if !retry(&err, Some(&current), iterations, false) {
+                    return Err(Self::command_error(
+                        err,
+                        node.as_ref(),
+                        iterations,
+                        sent,
+                        is_write,
+                    ));
+                }
+                last_err = Some(err);
+                continue;
+            }
+
+            let (socket_timeout, server_timeout) = commands::attempt_timeouts(policy, deadline);
+            let mut conn = match current.get_connection(socket_timeout) {
                 Ok(conn) => conn,
                 Err(err) => {
-                    warn!("Node {}: {}", node, err);
+                    warn!("Node {}: {}", current, err);
+                    commands::add_node_error(&current, &err);
+                    if !retry(&err, Some(&current), iterations, false) {
+                        return Err(Self::command_error(
+                            err,
+                            node.as_ref(),
+                            iterations,
+                            sent,
+                            is_write,
+                        ));
+                    }
+                    last_err = Some(err);
                     continue;
                 }
             };
 
-            cmd.prepare_buffer(&mut conn)
-                .chain_err(|| "Failed to prepare send buffer")?;
-            cmd.write_timeout(&mut conn, policy.timeout())
-                .chain_err(|| "Failed to set timeout for send buffer")?;
+            conn.buffer.supports_bool = current.supports_bool();
+            conn.buffer.filter_override = filter.cloned();
+            let prepared = cmd
+                .prepare_buffer(&mut conn)
+                .chain_err(|| "Failed to prepare send buffer")
+                .and_then(|()| {
+                    cmd.write_timeout(&mut conn, server_timeout)
+                        .chain_err(|| "Failed to set timeout for send buffer")
+                });
+            // never leave the override on the pooled connection for the next command
+            conn.buffer.filter_override = None;
+            if let Err(err) = prepared {
+                return Err(Self::command_error(
+                    err,
+                    node.as_ref(),
+                    iterations,
+                    sent,
+                    is_write,
+                ));
+            }
 
             // Send command.
+            sent += 1;
             if let Err(err) = cmd.write_buffer(&mut conn) {
                 // IO errors are considered temporary anomalies. Retry.
                 // Close socket to flush out possible garbage. Do not put back in pool.
                 conn.invalidate();
-                warn!("Node {}: {}", node, err);
+                warn!("Node {}: {}", current, err);
+                commands::add_node_error(&current, &err);
+                if !retry(&err, Some(&current), iterations, true) {
+                    return Err(Self::command_error(
+                        err,
+                        node.as_ref(),
+                        iterations,
+                        sent,
+                        is_write,
+                    ));
+                }
+                last_err = Some(err);
                 continue;
             }
 
@@ -134,13 +279,66 @@ impl<'a> SingleCommand<'a> {
                 if !commands::keep_connection(&err) {
                     conn.invalidate();
                 }
-                return Err(err);
+                commands::add_node_error(&current, &err);
+
+                // Socket timeouts are retried on another attempt; other errors only if a retry
+                // policy asks for it. Without a retry policy, a write that timed out is not
+                // retried, since it might have been applied. Commands that stream their results
+                // cannot be retried once the response has been parsed.
+                let retryable = match retry_policy {
+                    Some(_) => true,
+                    None => !is_write && commands::is_socket_timeout(&err),
+                };
+                if retryable
+                    && cmd.can_retry_response()
+                    && retry(&err, Some(&current), iterations, true)
+                {
+                    last_err = Some(err);
+                    continue;
+                }
+                return Err(Self::command_error(
+                    err,
+                    node.as_ref(),
+                    iterations,
+                    sent,
+                    is_write,
+                ));
             }
 
             // command has completed successfully.  Exit method.
             return Ok(());
-        }
+        };
+
+        let err = match last_err {
+            Some(last_err) => Error::with_chain(last_err, err),
+            None => err.into(),
+        };
+        Err(Self::command_error(
+            err,
+            node.as_ref(),
+            iterations,
+            sent,
+            is_write,
+        ))
+    }
 
-        bail!(ErrorKind::Connection("Timeout".to_string()))
+    // Attaches the context of the failed command to the error. A write is in doubt if it has been
+    // sent more than once, or if it has been sent once and no definite response was received from
+    // the server.
+    fn command_error(
+        err: Error,
+        node: Option<&Arc<Node>>,
+        iterations: usize,
+        sent: usize,
+        is_write: bool,
+    ) -> Error {
+        let definite = match *err.kind() {
+            ErrorKind::ServerError(result_code) => result_code != ResultCode::Timeout,
+            ErrorKind::UdfBadResponse(_) => true,
+            _ => false,
+        };
+        let in_doubt = is_write && (sent > 1 || (sent == 1 && !definite));
+        let node = node.map(|node| node.name().to_owned());
+        err.with_context(node, iterations, in_doubt)
     }
 }
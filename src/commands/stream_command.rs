@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::thread;
 use std::time::Duration;
 
@@ -21,27 +21,90 @@ use crate::cluster::Node;
 use crate::commands::buffer;
 use crate::commands::field_type::FieldType;
 use crate::commands::Command;
-use crate::errors::{ErrorKind, Result};
+use crate::errors::{Error, ErrorKind, Result};
+use crate::metrics::CommandType;
 use crate::net::Connection;
-use crate::query::Recordset;
+use crate::policy::ClientPolicy;
+use crate::query::{ControlFlow, JobType, Recordset};
 use crate::value::bytes_to_particle;
-use crate::{Key, Record, ResultCode, Value};
+use crate::{Key, Record, RecordRef, ResultCode, Value};
 
-pub struct StreamCommand {
+// Callback receiving records decoded in place from the connection buffer.
+pub type RecordRefCallback<'a> = dyn Fn(RecordRef) -> ControlFlow + Sync + 'a;
+
+// Callback receiving owned records.
+pub type RecordCallback<'a> = dyn Fn(Record) -> ControlFlow + Sync + 'a;
+
+// Determines what happens to the records parsed by a stream command.
+#[derive(Clone, Copy)]
+pub enum RecordHandler<'a> {
+    // Records are pushed onto the recordset queue.
+    Queue,
+    // Records are passed to the callback, decoded in place.
+    Borrowed(&'a RecordRefCallback<'a>),
+    // Records are passed to the callback.
+    Owned(&'a RecordCallback<'a>),
+}
+
+pub struct StreamCommand<'a> {
     node: Arc<Node>,
-    pub recordset: Arc<Recordset>,
+    // Only a weak reference is held, so that the recordset gets dropped, and the job aborted on
+    // the server, as soon as the caller drops it.
+    recordset: Weak<Recordset>,
+    task_id: u64,
+    job_type: JobType,
+    handler: RecordHandler<'a>,
 }
 
-impl Drop for StreamCommand {
+impl<'a> Drop for StreamCommand<'a> {
     fn drop(&mut self) {
         // signal_end
-        self.recordset.signal_end();
+        if let Some(recordset) = self.recordset.upgrade() {
+            recordset.signal_node_end(&self.node);
+        }
     }
 }
 
-impl StreamCommand {
-    pub fn new(node: Arc<Node>, recordset: Arc<Recordset>) -> Self {
-        StreamCommand { node, recordset }
+impl<'a> StreamCommand<'a> {
+    pub fn new(node: Arc<Node>, recordset: &Arc<Recordset>, handler: RecordHandler<'a>) -> Self {
+        StreamCommand {
+            node,
+            recordset: Arc::downgrade(recordset),
+            task_id: recordset.task_id(),
+            job_type: recordset.job_type(),
+            handler,
+        }
+    }
+
+    pub const fn task_id(&self) -> u64 {
+        self.task_id
+    }
+
+    // Returns the recordset, or an error if the caller has closed or dropped it in the meantime.
+    fn active_recordset(&self) -> Result<Arc<Recordset>> {
+        match self.recordset.upgrade() {
+            Some(recordset) if recordset.is_active() => Ok(recordset),
+            _ => bail!(self.job_type.abort_error()),
+        }
+    }
+
+    // Abort errors caused by the caller closing or dropping the recordset are expected and are not
+    // reported; any other error is.
+    pub fn finish(&self, result: Result<()>) -> Result<()> {
+        match result {
+            Err(ref err) if self.is_caller_abort(err) => Ok(()),
+            result => result,
+        }
+    }
+
+    // Whether the error is the abort error of a job the caller has stopped, by closing or dropping
+    // the recordset or by breaking out of the record callback.
+    pub fn is_caller_abort(&self, err: &Error) -> bool {
+        let is_abort_error = match (err.kind(), self.job_type.abort_error()) {
+            (ErrorKind::ServerError(rc), ErrorKind::ServerError(abort_rc)) => *rc == abort_rc,
+            _ => false,
+        };
+        is_abort_error && self.active_recordset().is_err()
     }
 
     fn parse_record(conn: &mut Connection, size: usize) -> Result<(Option<Record>, bool)> {
@@ -101,32 +164,130 @@ impl StreamCommand {
         Ok((Some(record), true))
     }
 
+    // Reads the remainder of the record into the connection buffer, appending all fields and
+    // operations to the record header, and decodes it in place.
+    fn parse_record_ref(
+        conn: &mut Connection,
+        size: usize,
+    ) -> Result<(Option<RecordRef<'_>>, bool)> {
+        let result_code = ResultCode::from(conn.buffer.read_u8(Some(5))?);
+        if result_code != ResultCode::Ok {
+            if conn.bytes_read() < size {
+                let remaining = size - conn.bytes_read();
+                conn.read_buffer(remaining)?;
+            }
+
+            match result_code {
+                ResultCode::KeyNotFoundError => return Ok((None, false)),
+                _ => bail!(ErrorKind::ServerError(result_code)),
+            }
+        }
+
+        let info3 = conn.buffer.read_u8(Some(3))?;
+        if info3 & buffer::INFO3_LAST == buffer::INFO3_LAST {
+            return Ok((None, false));
+        }
+
+        let field_count = conn.buffer.read_u16(Some(18))? as usize;
+        let op_count = conn.buffer.read_u16(Some(20))? as usize;
+        for _ in 0..field_count {
+            conn.append_buffer(4)?;
+            let field_len = conn
+                .buffer
+                .read_u32(Some(conn.buffer.data_buffer.len() - 4))?;
+            conn.append_buffer(field_len as usize)?;
+        }
+
+        for _ in 0..op_count {
+            conn.append_buffer(8)?;
+            let op_size = conn
+                .buffer
+                .read_u32(Some(conn.buffer.data_buffer.len() - 8))?;
+            conn.append_buffer((op_size as usize).saturating_sub(4))?;
+        }
+
+        if info3 & buffer::_INFO3_PARTITION_DONE != 0 {
+            return Ok((None, true));
+        }
+
+        let record = RecordRef::parse(&conn.buffer.data_buffer)?;
+        Ok((Some(record), true))
+    }
+
+    // Pushes the record onto the recordset queue, waiting for the caller to consume records if the
+    // queue is full.
+    fn push_record(&self, mut rec: Record) -> Result<()> {
+        loop {
+            match self.active_recordset()?.push(Ok(rec)) {
+                None => return Ok(()),
+                Some(returned) => {
+                    rec = returned?;
+                    thread::yield_now();
+                }
+            }
+        }
+    }
+
+    // Passes an error that ended the command on to the caller through the recordset queue,
+    // waiting for the caller to consume records if the queue is full. The error is dropped if the
+    // caller has closed or dropped the recordset in the meantime.
+    pub fn push_error(&self, err: Error) {
+        let mut result = Err(err);
+        while let Ok(recordset) = self.active_recordset() {
+            match recordset.push(result) {
+                None => return,
+                Some(returned) => {
+                    result = returned;
+                    thread::yield_now();
+                }
+            }
+        }
+    }
+
     fn parse_stream(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
-        while self.recordset.is_active() && conn.bytes_read() < size {
+        while conn.bytes_read() < size {
+            // Stop reading from the socket once the recordset has been closed.
+            self.active_recordset()?;
+
             // Read header.
             if let Err(err) = conn.read_buffer(buffer::MSG_REMAINING_HEADER_SIZE as usize) {
                 warn!("Parse result error: {}", err);
                 return Err(err);
             }
 
-            let res = StreamCommand::parse_record(conn, size);
-            match res {
-                Ok((Some(mut rec), _)) => loop {
-                    let result = self.recordset.push(Ok(rec));
-                    match result {
-                        None => break,
-                        Some(returned) => {
-                            rec = returned?;
-                            thread::yield_now();
+            let flow = match self.handler {
+                RecordHandler::Queue => match StreamCommand::parse_record(conn, size) {
+                    Ok((Some(rec), _)) => {
+                        self.push_record(rec)?;
+                        ControlFlow::Continue
+                    }
+                    Ok((None, cont)) => return Ok(cont),
+                    Err(err) => {
+                        if let Some(recordset) = self.recordset.upgrade() {
+                            recordset.push(Err(err));
                         }
+                        return Ok(false);
                     }
                 },
-                Ok((None, cont)) => return Ok(cont),
-                Err(err) => {
-                    self.recordset.push(Err(err));
-                    return Ok(false);
+                RecordHandler::Borrowed(callback) => {
+                    match StreamCommand::parse_record_ref(conn, size)? {
+                        (Some(rec), _) => callback(rec),
+                        (None, true) => ControlFlow::Continue,
+                        (None, false) => return Ok(false),
+                    }
                 }
+                RecordHandler::Owned(callback) => match StreamCommand::parse_record(conn, size)? {
+                    (Some(rec), _) => callback(rec),
+                    (None, true) => ControlFlow::Continue,
+                    (None, false) => return Ok(false),
+                },
             };
+
+            if flow.is_break() {
+                // Abort the job on all nodes; the error is not reported to the caller.
+                self.active_recordset()?.close();
+                bail!(self.job_type.abort_error());
+            }
         }
 
         Ok(true)
@@ -176,7 +337,7 @@ impl StreamCommand {
     }
 }
 
-impl Command for StreamCommand {
+impl<'a> Command for StreamCommand<'a> {
     fn write_timeout(&mut self, conn: &mut Connection, timeout: Option<Duration>) -> Result<()> {
         conn.buffer.write_timeout(timeout);
         Ok(())
@@ -186,6 +347,17 @@ impl Command for StreamCommand {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        match self.job_type {
+            JobType::Scan => CommandType::Scan,
+            JobType::Query => CommandType::Query,
+        }
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.node.client_policy()
+    }
+
     #[allow(unused_variables)]
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         // should be implemented downstream
@@ -212,4 +384,8 @@ impl Command for StreamCommand {
 
         Ok(())
     }
+
+    fn can_retry_response(&self) -> bool {
+        false
+    }
 }
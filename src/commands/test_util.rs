@@ -0,0 +1,209 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Fixtures for unit tests that execute commands against a node without a server.
+
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::node_validator::NodeValidator;
+use crate::cluster::{Cluster, Node};
+use crate::commands::Command;
+use crate::errors::Result;
+use crate::metrics::CommandType;
+use crate::net::{Connection, Host};
+use crate::policy::ClientPolicy;
+use crate::transport::{Stream, Transport};
+use crate::{Client, Key};
+
+// Fails every connection attempt, so that each command exhausts its retries.
+#[derive(Debug, Default)]
+pub struct RefusingTransport {
+    pub connects: AtomicUsize,
+}
+
+impl Transport for RefusingTransport {
+    fn connect(&self, _host: &Host, _timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+        self.connects.fetch_add(1, Ordering::SeqCst);
+        Err(io::Error::from(io::ErrorKind::ConnectionRefused))
+    }
+}
+
+// Client policy connecting through the given refusing transport.
+pub fn refusing_policy(transport: Arc<RefusingTransport>) -> ClientPolicy {
+    ClientPolicy {
+        transport,
+        ..ClientPolicy::default()
+    }
+}
+
+// Answers info commands with the response returned by the handler for each command.
+#[derive(Clone)]
+pub struct InfoTransport {
+    handler: Arc<dyn Fn(&str) -> String + Send + Sync>,
+}
+
+impl InfoTransport {
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        InfoTransport {
+            handler: Arc::new(handler),
+        }
+    }
+
+    // Client policy connecting through this transport.
+    pub fn policy(&self) -> ClientPolicy {
+        ClientPolicy {
+            transport: Arc::new(self.clone()),
+            ..ClientPolicy::default()
+        }
+    }
+}
+
+impl fmt::Debug for InfoTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InfoTransport").finish()
+    }
+}
+
+impl Transport for InfoTransport {
+    fn connect(&self, _host: &Host, _timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+        Ok(Box::new(InfoStream {
+            transport: self.clone(),
+            response: vec![],
+            pos: 0,
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct InfoStream {
+    transport: InfoTransport,
+    response: Vec<u8>,
+    pos: usize,
+}
+
+impl Stream for InfoStream {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        let end = self.pos + buf.len();
+        if end > self.response.len() {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+        buf.copy_from_slice(&self.response[self.pos..end]);
+        self.pos = end;
+        Ok(())
+    }
+
+    // Expects a complete info request: an 8 byte header followed by the commands, one per line.
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        let request = String::from_utf8_lossy(&buf[8..]);
+        let mut data = String::new();
+        for cmd in request.split('\n').filter(|cmd| !cmd.is_empty()) {
+            data.push_str(&format!("{}\t{}\n", cmd, (self.transport.handler)(cmd)));
+        }
+        let len = data.len() as u64;
+        self.response = vec![2, 1];
+        self.response.extend_from_slice(&len.to_be_bytes()[2..]);
+        self.response.extend_from_slice(data.as_bytes());
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn set_write_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Node named `BB9` that supports all server features.
+pub fn node(client_policy: ClientPolicy) -> Arc<Node> {
+    named_node("BB9", client_policy)
+}
+
+// Node with the given name that supports all server features.
+pub fn named_node(name: &str, client_policy: ClientPolicy) -> Arc<Node> {
+    let nv = NodeValidator {
+        name: name.to_owned(),
+        aliases: vec![Host::new("localhost", 3000)],
+        address: "127.0.0.1:3000".to_owned(),
+        client_policy: client_policy.clone(),
+        use_new_info: true,
+        supports_float: true,
+        supports_batch_index: true,
+        supports_replicas_all: true,
+        supports_geo: true,
+        supports_bool: true,
+    };
+    Arc::new(Node::new(client_policy, Arc::new(nv)))
+}
+
+// Client for the given nodes, without seeding or tending the cluster.
+pub fn client(client_policy: ClientPolicy, nodes: Vec<Arc<Node>>) -> Client {
+    Client::with_cluster(
+        &client_policy,
+        Cluster::with_nodes(client_policy.clone(), nodes),
+    )
+}
+
+// Command that only needs a connection to the node to succeed.
+pub struct StubCommand {
+    pub node: Arc<Node>,
+    pub key: Option<Key>,
+}
+
+impl Command for StubCommand {
+    fn write_timeout(&mut self, _conn: &mut Connection, _timeout: Option<Duration>) -> Result<()> {
+        Ok(())
+    }
+
+    fn prepare_buffer(&mut self, _conn: &mut Connection) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_node(&self) -> Result<Arc<Node>> {
+        Ok(self.node.clone())
+    }
+
+    fn parse_result(&mut self, _conn: &mut Connection) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_buffer(&mut self, _conn: &mut Connection) -> Result<()> {
+        Ok(())
+    }
+
+    fn command_type(&self) -> CommandType {
+        CommandType::Read
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.node.client_policy()
+    }
+
+    fn key(&self) -> Option<&Key> {
+        self.key.as_ref()
+    }
+}
@@ -19,7 +19,10 @@ use crate::cluster::{Cluster, Node};
 use crate::commands::buffer;
 use crate::commands::{Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
 use crate::net::Connection;
+use crate::policy::ClientPolicy;
 use crate::policy::WritePolicy;
 use crate::{Key, ResultCode};
 
@@ -51,6 +54,22 @@ impl<'a> Command for TouchCommand<'a> {
         conn.flush()
     }
 
+    fn command_type(&self) -> CommandType {
+        CommandType::Write
+    }
+
+    fn client_policy(&self) -> &ClientPolicy {
+        self.single_command.client_policy()
+    }
+
+    fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.policy.filter_expression().as_ref()
+    }
+
+    fn key(&self) -> Option<&Key> {
+        Some(self.single_command.key)
+    }
+
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
         conn.buffer.set_touch(self.policy, self.single_command.key)
     }
@@ -59,6 +78,10 @@ impl<'a> Command for TouchCommand<'a> {
         self.single_command.get_node()
     }
 
+    fn is_write(&self) -> bool {
+        true
+    }
+
     fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         // Read header.
         if let Err(err) = conn.read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize) {
@@ -38,6 +38,9 @@
 //!     },
 //!     Err(err) => {
 //!         println!("Error fetching record: {}", err);
+//!         if let Some(node) = err.node() {
+//!             println!("Node: {}, attempts: {}", node, err.iterations());
+//!         }
 //!         for err in err.iter().skip(1) {
 //!             println!("Caused by: {}", err);
 //!         }
@@ -49,9 +52,17 @@
 //!     }
 //! }
 //! ```
+//!
+//! Errors returned by database commands carry an `ErrorContext` as the first cause in their error
+//! chain, describing the node that served the command, the number of attempts made and whether a
+//! failed write might have been applied. Use `Error::node`, `Error::iterations` and
+//! `Error::in_doubt` to access it.
 
 #![allow(missing_docs)]
 
+use std::error::Error as StdError;
+use std::fmt;
+
 use crate::ResultCode;
 
 error_chain! {
@@ -103,6 +114,13 @@ error_chain! {
             display("Invalid cluster node: {}", details)
         }
 
+/// Exceeded max. number of errors allowed for a node within the error rate window; commands to
+/// the node are rejected until the window resets.
+        MaxErrorRate(node: String) {
+            description("Max. error rate exceeded")
+            display("Max. error rate exceeded for node {}", node)
+        }
+
 /// Exceeded max. number of connections per node.
         NoMoreConnections {
             description("Too many connections")
@@ -121,6 +139,12 @@ error_chain! {
             display("UDF Bad Response: {}", details)
         }
 
+/// A value could not be converted to or from the requested Rust type.
+        InvalidValue(details: String) {
+            description("Invalid Value")
+            display("Invalid value: {}", details)
+        }
+
 /// Error returned when a tasked timeed out before it could be completed.
         Timeout(details: String) {
             description("Timeout")
@@ -141,3 +165,186 @@ macro_rules! log_error_chain {
         }
     };
 }
+
+/// Context of a failed database command. Attached to the command's error as the first cause in the
+/// error chain; the original cause of the error, if any, is available as its source.
+#[derive(Debug)]
+pub struct ErrorContext {
+    node: Option<String>,
+    iterations: usize,
+    in_doubt: bool,
+    source: Option<Box<dyn StdError + Send>>,
+}
+
+impl ErrorContext {
+    /// Name of the node the command was last sent to, if a node had been selected.
+    pub fn node(&self) -> Option<&str> {
+        self.node.as_deref()
+    }
+
+    /// Number of attempts made to execute the command.
+    pub const fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Whether the command is a write that might have been applied on the server.
+    pub const fn in_doubt(&self) -> bool {
+        self.in_doubt
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Command failed after {} attempt(s)", self.iterations)?;
+        if let Some(ref node) = self.node {
+            write!(f, " on node {}", node)?;
+        }
+        if self.in_doubt {
+            write!(f, "; write may have been applied")?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for ErrorContext {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn StdError + 'static))
+    }
+}
+
+impl Error {
+    // Attaches the context of a failed command to the error, keeping its original cause.
+    pub(crate) fn with_context(
+        self,
+        node: Option<String>,
+        iterations: usize,
+        in_doubt: bool,
+    ) -> Self {
+        let Error(kind, state) = self;
+        let context = ErrorContext {
+            node,
+            iterations,
+            in_doubt,
+            source: state.next_error,
+        };
+        Error(
+            kind,
+            error_chain::State {
+                next_error: Some(Box::new(context)),
+                backtrace: state.backtrace,
+            },
+        )
+    }
+
+    /// Returns the context of the failed command, if the error was returned by a database command.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        let mut next = self.1.next_error.as_ref()?;
+        loop {
+            if let Some(context) = next.downcast_ref::<ErrorContext>() {
+                return Some(context);
+            }
+            next = next.downcast_ref::<Error>()?.1.next_error.as_ref()?;
+        }
+    }
+
+    /// Name of the node the failed command was last sent to.
+    pub fn node(&self) -> Option<&str> {
+        self.context().and_then(ErrorContext::node)
+    }
+
+    /// Number of attempts made to execute the failed command, or zero if the error was not
+    /// returned by a database command.
+    pub fn iterations(&self) -> usize {
+        self.context().map_or(0, ErrorContext::iterations)
+    }
+
+    /// Whether the failed command is a write that might have been applied on the server, e.g.
+    /// because the connection timed out after the command had been sent.
+    pub fn in_doubt(&self) -> bool {
+        self.context().map_or(false, ErrorContext::in_doubt)
+    }
+
+    /// Result code returned by the server, if the error is a server error.
+    pub fn result_code(&self) -> Option<ResultCode> {
+        match *self.kind() {
+            ErrorKind::ServerError(result_code) => Some(result_code),
+            _ => None,
+        }
+    }
+
+    /// Whether the error is transient, i.e. retrying the command might succeed.
+    pub fn is_retryable(&self) -> bool {
+        match *self.kind() {
+            ErrorKind::Connection(_)
+            | ErrorKind::Io(_)
+            | ErrorKind::InvalidNode(_)
+            | ErrorKind::MaxErrorRate(_)
+            | ErrorKind::NoMoreConnections
+            | ErrorKind::Timeout(_) => true,
+            ErrorKind::ServerError(result_code) => matches!(
+                result_code,
+                ResultCode::Timeout
+                    | ResultCode::KeyBusy
+                    | ResultCode::DeviceOverload
+                    | ResultCode::ServerNotAvailable
+                    | ResultCode::ServerMemError
+                    | ResultCode::BatchQueuesFull
+                    | ResultCode::QueryQueueFull
+                    | ResultCode::QueryTimeout
+            ),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as StdError;
+
+    use super::{Error, ErrorKind, ResultExt};
+    use crate::ResultCode;
+
+    #[test]
+    fn context() {
+        let err: Error = ErrorKind::ServerError(ResultCode::KeyNotFoundError).into();
+        assert_eq!(err.result_code(), Some(ResultCode::KeyNotFoundError));
+        assert_eq!(err.node(), None);
+        assert_eq!(err.iterations(), 0);
+        assert!(!err.in_doubt());
+        assert!(!err.is_retryable());
+
+        let cause: Result<(), Error> = Err(ErrorKind::Connection("Broken pipe".to_string()).into());
+        let err = cause
+            .chain_err(|| ErrorKind::Timeout("Command timed out".to_string()))
+            .unwrap_err()
+            .with_context(Some("BB9".to_string()), 3, true);
+        assert!(matches!(err.kind(), ErrorKind::Timeout(_)));
+        assert_eq!(err.node(), Some("BB9"));
+        assert_eq!(err.iterations(), 3);
+        assert!(err.in_doubt());
+        assert!(err.is_retryable());
+        assert_eq!(err.result_code(), None);
+
+        let causes: Vec<String> = err.iter().skip(1).map(ToString::to_string).collect();
+        assert_eq!(
+            causes,
+            vec![
+                "Command failed after 3 attempt(s) on node BB9; write may have been applied",
+                "Unable to communicate with server cluster: Broken pipe",
+            ]
+        );
+        assert!(err.source().is_some());
+
+        // The context is found through errors chained by the caller.
+        let outer: Result<(), Error> = Err(err);
+        let outer = outer.chain_err(|| "Failed to read record").unwrap_err();
+        assert_eq!(outer.node(), Some("BB9"));
+
+        // Commands rejected because of the node's error rate are retried.
+        let err: Error = ErrorKind::MaxErrorRate("BB9".to_string()).into();
+        assert!(err.is_retryable());
+        assert_eq!(err.to_string(), "Max. error rate exceeded for node BB9");
+    }
+}
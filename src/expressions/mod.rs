@@ -710,6 +710,16 @@ pub fn geo_val(val: String) -> FilterExpression {
 pub fn nil() -> FilterExpression {
     FilterExpression::new(None, Some(Value::Nil), None, None, None, None)
 }
+
+/// Create an Infinity Value, e.g. as open upper bound of value range expressions.
+pub fn infinity() -> FilterExpression {
+    FilterExpression::new(None, Some(Value::Infinity), None, None, None, None)
+}
+
+/// Create a Wildcard Value, which matches any value in list and map value expressions.
+pub fn wildcard() -> FilterExpression {
+    FilterExpression::new(None, Some(Value::Wildcard), None, None, None, None)
+}
 /// Create "not" operator expression.
 /// ```
 /// // ! (a == 0 || a == 10)
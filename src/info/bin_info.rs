@@ -0,0 +1,76 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::info::{parse_pairs, value_of};
+
+/// Bin name usage of a namespace as returned by the `bins/<ns>` info command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinInfo {
+    /// Name of the namespace.
+    pub namespace: String,
+
+    /// Number of distinct bin names in use in the namespace.
+    pub num_bin_names: u64,
+
+    /// Maximum number of distinct bin names the namespace can hold. Zero if the server did not
+    /// report a quota.
+    pub bin_names_quota: u64,
+
+    /// Bin names in use in the namespace.
+    pub bin_names: Vec<String>,
+}
+
+impl BinInfo {
+    /// Parse the response of the `bins/<ns>` info command, e.g.
+    /// `bin_names=2,bin_names_quota=65535,foo,bar`.
+    pub fn parse(namespace: &str, response: &str) -> Self {
+        let (counters, bin_names): (Vec<&str>, Vec<&str>) = response
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .partition(|entry| entry.contains('='));
+        let values = parse_pairs(&counters.join(","), ',');
+
+        BinInfo {
+            namespace: namespace.to_string(),
+            num_bin_names: value_of(&values, &["bin_names", "num-bin-names"])
+                .unwrap_or(bin_names.len() as u64),
+            bin_names_quota: value_of(&values, &["bin_names_quota", "bin-names-quota"])
+                .unwrap_or_default(),
+            bin_names: bin_names.into_iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinInfo;
+
+    #[test]
+    fn parse_bins() {
+        let info = BinInfo::parse("test", "bin_names=3,bin_names_quota=65535,a,bb,ccc");
+        assert_eq!(info.namespace, "test");
+        assert_eq!(info.num_bin_names, 3);
+        assert_eq!(info.bin_names_quota, 65535);
+        assert_eq!(info.bin_names, vec!["a", "bb", "ccc"]);
+    }
+
+    #[test]
+    fn parse_no_bins() {
+        let info = BinInfo::parse("test", "bin_names=0,bin_names_quota=32768");
+        assert_eq!(info.num_bin_names, 0);
+        assert!(info.bin_names.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt;
+
+use crate::errors::{ErrorKind, Result};
+
+/// Server version as returned by the `build` info command.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BuildInfo {
+    /// Major version number.
+    pub major: u32,
+
+    /// Minor version number.
+    pub minor: u32,
+
+    /// Patch version number.
+    pub patch: u32,
+
+    /// Build number.
+    pub build: u32,
+}
+
+impl BuildInfo {
+    /// Parse the response of the `build` info command, e.g. `5.6.0.3`. Pre-release suffixes
+    /// such as `-rc1` are ignored.
+    pub fn parse(response: &str) -> Result<Self> {
+        let mut parts = response.trim().split('.').map(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<u32>()
+        });
+        let mut next = || parts.next().map_or(Ok(0), |part| part);
+
+        match (next(), next(), next(), next()) {
+            (Ok(major), Ok(minor), Ok(patch), Ok(build)) => Ok(BuildInfo {
+                major,
+                minor,
+                patch,
+                build,
+            }),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Invalid server build version: {}",
+                response
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuildInfo;
+
+    #[test]
+    fn parse_build() {
+        let build = BuildInfo::parse("5.6.0.3").unwrap();
+        assert_eq!(build.major, 5);
+        assert_eq!(build.minor, 6);
+        assert_eq!(build.patch, 0);
+        assert_eq!(build.build, 3);
+        assert_eq!(build.to_string(), "5.6.0.3");
+    }
+
+    #[test]
+    fn parse_short_and_suffixed_builds() {
+        assert_eq!(BuildInfo::parse("4.9").unwrap().to_string(), "4.9.0.0");
+        assert_eq!(BuildInfo::parse("6.0.0.0-rc1").unwrap().major, 6);
+        assert!(BuildInfo::parse("").is_err());
+        assert!(BuildInfo::parse("unknown").is_err());
+    }
+
+    #[test]
+    fn compare_builds() {
+        assert!(BuildInfo::parse("5.6.0.3").unwrap() > BuildInfo::parse("5.5.0.9").unwrap());
+    }
+}
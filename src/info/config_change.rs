@@ -0,0 +1,480 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::Node;
+use crate::errors::{ErrorKind, Result};
+use crate::info::{check_response, parse_pairs};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigContext {
+    Service,
+    Namespace(String),
+}
+
+/// A dynamic configuration change, applied to all cluster nodes through `Client::set_config`
+/// using the `set-config` info command.
+///
+/// # Examples
+///
+/// ```rust
+/// use aerospike::info::ConfigChange;
+///
+/// let change = ConfigChange::namespace("test")
+///     .set("default-ttl", 3600)
+///     .set("nsup-period", 120)
+///     .rollback_on_failure(true);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    context: ConfigContext,
+    params: Vec<(String, String)>,
+    rollback: bool,
+}
+
+impl ConfigChange {
+    /// Create a configuration change for the `service` context.
+    pub fn service() -> Self {
+        ConfigChange::new(ConfigContext::Service)
+    }
+
+    /// Create a configuration change for the given namespace.
+    pub fn namespace(namespace: &str) -> Self {
+        ConfigChange::new(ConfigContext::Namespace(namespace.to_string()))
+    }
+
+    const fn new(context: ConfigContext) -> Self {
+        ConfigChange {
+            context,
+            params: vec![],
+            rollback: false,
+        }
+    }
+
+    /// Set the configuration parameter `name` to the given value.
+    pub fn set<V: fmt::Display>(mut self, name: &str, value: V) -> Self {
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// If set to `true`, the previous configuration values are restored on all nodes if the
+    /// change could not be applied or verified on one or more nodes. The change is not applied
+    /// to nodes that do not report the current value of every changed parameter, since it could
+    /// not be rolled back there. Default: `false`.
+    pub const fn rollback_on_failure(mut self, rollback: bool) -> Self {
+        self.rollback = rollback;
+        self
+    }
+
+    /// Returns the configuration parameters changed by this configuration change.
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+
+    fn context_params(&self) -> String {
+        match self.context {
+            ConfigContext::Service => "context=service".to_string(),
+            ConfigContext::Namespace(ref namespace) => {
+                format!("context=namespace;id={}", namespace)
+            }
+        }
+    }
+
+    fn get_command(&self) -> String {
+        format!("get-config:{}", self.context_params())
+    }
+
+    fn set_command(&self, name: &str, value: &str) -> String {
+        format!("set-config:{};{}={}", self.context_params(), name, value)
+    }
+}
+
+/// Outcome of a configuration change on a single cluster node.
+#[derive(Debug)]
+pub struct ConfigResult {
+    /// Name of the cluster node.
+    pub node: String,
+
+    /// Whether the change was applied and verified successfully on the node.
+    pub result: Result<()>,
+
+    /// Values of the changed parameters before the change was applied.
+    pub previous: HashMap<String, String>,
+
+    /// Whether the previous values of all parameters written to the node have been restored
+    /// after the change failed on one or more nodes. Nodes the change was not written to are not
+    /// rolled back.
+    pub rolled_back: bool,
+}
+
+// Applies the configuration change to all given nodes, verifies it by reading the configuration
+// back and, if requested, rolls it back on all nodes if it failed on any of them.
+pub(crate) fn apply(
+    nodes: &[Arc<Node>],
+    change: &ConfigChange,
+    timeout: Option<Duration>,
+) -> Vec<ConfigResult> {
+    // parameters written to each node, including the one whose write failed, since the server
+    // might have applied it anyway
+    let mut written = Vec::with_capacity(nodes.len());
+    let mut results: Vec<ConfigResult> = nodes
+        .iter()
+        .map(|node| {
+            let mut previous = HashMap::new();
+            let mut node_written = vec![];
+            let result = read_config(node, change, timeout)
+                .and_then(|config| {
+                    for (name, _) in &change.params {
+                        match config.get(name) {
+                            Some(value) => {
+                                previous.insert(name.clone(), value.clone());
+                            }
+                            None if change.rollback => bail!(ErrorKind::BadResponse(format!(
+                                "Config parameter `{}` not found; the change could not be rolled back",
+                                name
+                            ))),
+                            None => {}
+                        }
+                    }
+                    Ok(())
+                })
+                .and_then(|_| {
+                    for (name, value) in &change.params {
+                        node_written.push(name.clone());
+                        write_param(node, change, name, value, timeout)?;
+                    }
+                    Ok(())
+                })
+                .and_then(|_| verify_config(node, change, timeout));
+            written.push(node_written);
+
+            ConfigResult {
+                node: node.name().to_string(),
+                result,
+                previous,
+                rolled_back: false,
+            }
+        })
+        .collect();
+
+    if change.rollback && results.iter().any(|res| res.result.is_err()) {
+        for ((node, res), written) in nodes.iter().zip(results.iter_mut()).zip(written) {
+            // nothing to restore on nodes that were not changed
+            if written.is_empty() {
+                continue;
+            }
+            let mut restored = true;
+            for name in &written {
+                let restore = match res.previous.get(name) {
+                    Some(value) => write_param(node, change, name, value, timeout),
+                    None => {
+                        Err(ErrorKind::BadResponse("previous value unknown".to_string()).into())
+                    }
+                };
+                if let Err(err) = restore {
+                    warn!(
+                        "Node {}: failed to roll back config parameter `{}`: {}",
+                        node, name, err
+                    );
+                    restored = false;
+                }
+            }
+            res.rolled_back = restored;
+        }
+    }
+
+    results
+}
+
+fn read_config(
+    node: &Node,
+    change: &ConfigChange,
+    timeout: Option<Duration>,
+) -> Result<HashMap<String, String>> {
+    let cmd = change.get_command();
+    let response = node.info(timeout, &[&cmd])?;
+    match response.get(&cmd) {
+        Some(value) => {
+            check_response(&cmd, value)?;
+            Ok(parse_pairs(value, ';'))
+        }
+        None => bail!(ErrorKind::BadResponse(format!(
+            "Missing response for info command `{}`",
+            cmd
+        ))),
+    }
+}
+
+fn write_param(
+    node: &Node,
+    change: &ConfigChange,
+    name: &str,
+    value: &str,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let cmd = change.set_command(name, value);
+    let response = node.info(timeout, &[&cmd])?;
+    match response.get(&cmd) {
+        Some(value) if value.eq_ignore_ascii_case("ok") => Ok(()),
+        Some(value) => bail!(ErrorKind::BadResponse(format!(
+            "Info command `{}` failed: {}",
+            cmd, value
+        ))),
+        None => bail!(ErrorKind::BadResponse(format!(
+            "Missing response for info command `{}`",
+            cmd
+        ))),
+    }
+}
+
+fn verify_config(node: &Node, change: &ConfigChange, timeout: Option<Duration>) -> Result<()> {
+    let config = read_config(node, change, timeout)?;
+    for (name, expected) in &change.params {
+        match config.get(name) {
+            Some(actual) if values_match(name, expected, actual) => {}
+            actual => bail!(ErrorKind::BadResponse(format!(
+                "Config parameter `{}` not applied: expected {}, got {:?}",
+                name, expected, actual
+            ))),
+        }
+    }
+    Ok(())
+}
+
+// Binary size units and time units accepted by the server for numeric config values.
+const SIZE_UNITS: &[(char, u64)] = &[
+    ('k', 1 << 10),
+    ('m', 1 << 20),
+    ('g', 1 << 30),
+    ('t', 1 << 40),
+    ('p', 1 << 50),
+];
+const TIME_UNITS: &[(char, u64)] = &[('s', 1), ('m', 60), ('h', 3600), ('d', 86400)];
+
+// Suffixes of the names of parameters that take a duration, e.g. `default-ttl` or `nsup-period`.
+// All other parameters take sizes; "m" means minutes for the former and MiB for the latter.
+const TIME_PARAM_SUFFIXES: &[&str] = &["ttl", "period", "age", "delay"];
+
+fn units(name: &str) -> &'static [(char, u64)] {
+    if TIME_PARAM_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+    {
+        TIME_UNITS
+    } else {
+        SIZE_UNITS
+    }
+}
+
+// Compares a config value set by the caller with the value reported by the server, which
+// normalizes numbers with units (e.g. "1G" to "1073741824") and booleans (e.g. "yes" to "true").
+// Units are interpreted according to the parameter `name`.
+fn values_match(name: &str, expected: &str, actual: &str) -> bool {
+    if expected.eq_ignore_ascii_case(actual) {
+        return true;
+    }
+    if let (Some(expected), Some(actual)) = (parse_bool(expected), parse_bool(actual)) {
+        return expected == actual;
+    }
+    let units = units(name);
+    match (parse_number(expected, units), parse_number(actual, units)) {
+        (Some(expected), Some(actual)) => expected == actual,
+        _ => false,
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "enabled" => Some(true),
+        "false" | "no" | "off" | "disabled" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_number(value: &str, units: &[(char, u64)]) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(number) = value.parse() {
+        return Some(number);
+    }
+    let suffix = value.chars().last()?.to_ascii_lowercase();
+    let (_, multiplier) = units.iter().find(|(unit, _)| *unit == suffix)?;
+    let number: u64 = value[..value.len() - 1].parse().ok()?;
+    number.checked_mul(*multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use super::{apply, values_match, ConfigChange};
+    use crate::cluster::Node;
+    use crate::commands::test_util::{self, InfoTransport};
+
+    // Node answering `get-config` and `set-config` from the given config, rejecting the change
+    // `reject`, given as `name=value`.
+    fn config_node(
+        name: &str,
+        config: &Arc<Mutex<HashMap<String, String>>>,
+        reject: &'static str,
+    ) -> Arc<Node> {
+        let config = config.clone();
+        let transport = InfoTransport::new(move |cmd| {
+            let mut config = config.lock().unwrap();
+            if cmd.starts_with("get-config:") {
+                let mut pairs: Vec<_> = config
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect();
+                pairs.sort();
+                return pairs.join(";");
+            }
+            let param = cmd.rsplit(';').next().unwrap();
+            if param == reject {
+                return "error".to_string();
+            }
+            let mut param = param.splitn(2, '=');
+            let (name, value) = (param.next().unwrap(), param.next().unwrap());
+            config.insert(name.to_string(), value.to_string());
+            "ok".to_string()
+        });
+        test_util::named_node(name, transport.policy())
+    }
+
+    fn config(pairs: &[(&str, &str)]) -> Arc<Mutex<HashMap<String, String>>> {
+        let config = pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Arc::new(Mutex::new(config))
+    }
+
+    #[test]
+    fn namespace_commands() {
+        let change = ConfigChange::namespace("test")
+            .set("default-ttl", 3600)
+            .set("allow-ttl-without-nsup", true);
+        assert_eq!(change.get_command(), "get-config:context=namespace;id=test");
+        assert_eq!(
+            change.set_command("default-ttl", "3600"),
+            "set-config:context=namespace;id=test;default-ttl=3600"
+        );
+        assert_eq!(
+            change.params(),
+            &[
+                ("default-ttl".to_string(), "3600".to_string()),
+                ("allow-ttl-without-nsup".to_string(), "true".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn service_commands() {
+        let change = ConfigChange::service().set("proto-fd-max", 20000);
+        assert_eq!(change.get_command(), "get-config:context=service");
+        assert_eq!(
+            change.set_command("proto-fd-max", "20000"),
+            "set-config:context=service;proto-fd-max=20000"
+        );
+    }
+
+    #[test]
+    fn normalized_values() {
+        assert!(values_match("memory-size", "1G", "1073741824"));
+        assert!(values_match("write-block-size", "64k", "65536"));
+        assert!(values_match("default-ttl", "1d", "86400"));
+        assert!(values_match("allow-ttl-without-nsup", "yes", "true"));
+        assert!(values_match("enable-benchmarks-read", "False", "false"));
+        assert!(values_match("default-ttl", "3600", "3600"));
+        assert!(values_match("conflict-resolution-policy", "Flat", "flat"));
+
+        assert!(!values_match("memory-size", "1G", "1000000000"));
+        assert!(!values_match("allow-ttl-without-nsup", "true", "false"));
+        assert!(!values_match("default-ttl", "3600", "7200"));
+        assert!(!values_match("memory-size", "1x", "1"));
+
+        // "m" means minutes for durations and MiB for sizes
+        assert!(values_match("nsup-period", "2m", "120"));
+        assert!(!values_match("nsup-period", "2m", "2097152"));
+        assert!(values_match("memory-size", "2m", "2097152"));
+        assert!(!values_match("memory-size", "2m", "120"));
+        assert!(!values_match("memory-size", "1d", "86400"));
+        assert!(!values_match("default-ttl", "1k", "1024"));
+    }
+
+    #[test]
+    fn rollback() {
+        let initial = [("default-ttl", "0"), ("nsup-period", "0")];
+        let config1 = config(&initial);
+        let config2 = config(&initial);
+        let nodes = vec![
+            config_node("A1", &config1, ""),
+            config_node("A2", &config2, "nsup-period=2m"),
+        ];
+        let change = ConfigChange::namespace("test")
+            .set("default-ttl", "1d")
+            .set("nsup-period", "2m")
+            .rollback_on_failure(true);
+
+        // The second parameter fails on the second node after the first one has been written;
+        // both nodes are restored.
+        let results = apply(&nodes, &change, None);
+        assert!(results[0].result.is_ok());
+        assert!(results[1].result.is_err());
+        assert!(results.iter().all(|res| res.rolled_back));
+        assert_eq!(results[1].previous["default-ttl"], "0");
+        for config in &[&config1, &config2] {
+            let config = config.lock().unwrap();
+            assert_eq!(config["default-ttl"], "0");
+            assert_eq!(config["nsup-period"], "0");
+        }
+
+        // Without rollback, the change remains applied where it succeeded.
+        let results = apply(&nodes, &change.clone().rollback_on_failure(false), None);
+        assert!(results[1].result.is_err());
+        assert!(results.iter().all(|res| !res.rolled_back));
+        assert_eq!(config1.lock().unwrap()["nsup-period"], "2m");
+        assert_eq!(config2.lock().unwrap()["default-ttl"], "1d");
+    }
+
+    #[test]
+    fn rollback_unknown_previous_value() {
+        let config1 = config(&[("default-ttl", "0"), ("nsup-period", "0")]);
+        let config2 = config(&[("default-ttl", "0")]);
+        let nodes = vec![
+            config_node("A1", &config1, ""),
+            config_node("A2", &config2, ""),
+        ];
+        let change = ConfigChange::namespace("test")
+            .set("default-ttl", 3600)
+            .set("nsup-period", 120)
+            .rollback_on_failure(true);
+
+        // The change is not written to the node that cannot be rolled back.
+        let results = apply(&nodes, &change, None);
+        assert!(results[0].result.is_ok());
+        assert!(results[0].rolled_back);
+        let err = results[1].result.as_ref().unwrap_err();
+        assert!(err.to_string().contains("`nsup-period` not found"));
+        assert!(!results[1].rolled_back);
+        assert_eq!(config1.lock().unwrap()["default-ttl"], "0");
+        assert_eq!(config2.lock().unwrap()["default-ttl"], "0");
+        assert!(!config2.lock().unwrap().contains_key("nsup-period"));
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::info::{parse_pairs, value_of};
+
+/// Server-side scan or query job as returned by the `scan-show` and `query-show` info commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobInfo {
+    /// Name of the node running the job.
+    pub node: String,
+
+    /// Transaction ID of the job, i.e. the task ID of the scan/query.
+    pub trid: u64,
+
+    /// Server module running the job, e.g. `scan` or `query`.
+    pub module: String,
+
+    /// Namespace the job is running on.
+    pub namespace: String,
+
+    /// Set the job is running on; empty if the job covers the whole namespace.
+    pub set_name: String,
+
+    /// Job status, e.g. `active(ok)`, `done(ok)` or `done(user-aborted)`.
+    pub status: String,
+
+    /// Job progress in percent.
+    pub progress: f64,
+
+    /// Number of records read by the job so far.
+    pub records_read: u64,
+
+    /// Job priority.
+    pub priority: u64,
+
+    /// All values returned by the server for the job, including the ones listed above.
+    pub values: HashMap<String, String>,
+}
+
+impl JobInfo {
+    /// Parse a single job entry of the `scan-show` or `query-show` info command response, i.e. a
+    /// list of `key=value` pairs separated by colons. `module` is used if the server does not
+    /// report the module of the job.
+    pub fn parse(node: &str, module: &str, entry: &str) -> Self {
+        let values = parse_pairs(entry, ':');
+        JobInfo {
+            node: node.to_string(),
+            trid: value_of(&values, &["trid"]).unwrap_or_default(),
+            module: value_of(&values, &["module"]).unwrap_or_else(|| module.to_string()),
+            namespace: value_of(&values, &["ns"]).unwrap_or_default(),
+            set_name: value_of(&values, &["set"]).unwrap_or_default(),
+            status: value_of(&values, &["status"]).unwrap_or_default(),
+            progress: value_of(&values, &["job-progress"]).unwrap_or_default(),
+            records_read: value_of(&values, &["recs-read", "recs-succeeded", "recs_read"])
+                .unwrap_or_default(),
+            priority: value_of(&values, &["priority"]).unwrap_or_default(),
+            values,
+        }
+    }
+
+    /// Parse the response of the `scan-show` or `query-show` info command into a list of jobs.
+    pub fn parse_list(node: &str, module: &str, response: &str) -> Vec<Self> {
+        response
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| JobInfo::parse(node, module, entry))
+            .collect()
+    }
+
+    /// Returns `true` if the job is still running.
+    pub fn is_active(&self) -> bool {
+        self.status.starts_with("active")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JobInfo;
+
+    #[test]
+    fn parse_jobs() {
+        let response = "module=scan:trid=1234:job-type=basic:ns=test:set=demo:priority=2:\
+                        status=active(ok):job-progress=45.50:run-time=1200:recs-read=500;\
+                        module=scan:trid=42:ns=test:set=:status=done(ok):job-progress=100.00:\
+                        recs-read=7";
+        let jobs = JobInfo::parse_list("BB9", "scan", response);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].node, "BB9");
+        assert_eq!(jobs[0].trid, 1234);
+        assert_eq!(jobs[0].module, "scan");
+        assert_eq!(jobs[0].namespace, "test");
+        assert_eq!(jobs[0].set_name, "demo");
+        assert_eq!(jobs[0].priority, 2);
+        assert!((jobs[0].progress - 45.5).abs() < f64::EPSILON);
+        assert_eq!(jobs[0].records_read, 500);
+        assert!(jobs[0].is_active());
+        assert_eq!(jobs[1].set_name, "");
+        assert!(!jobs[1].is_active());
+    }
+
+    #[test]
+    fn parse_jobs_without_module() {
+        let jobs = JobInfo::parse_list(
+            "BB9",
+            "query",
+            "trid=99:ns=test:set=demo:status=active(ok):job-progress=10.00:recs-succeeded=3;",
+        );
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].module, "query");
+        assert_eq!(jobs[0].records_read, 3);
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Types for the parsed responses of the server's info protocol.
+//!
+//! Info commands return plain text responses, usually in the form `key=value;key=value`. The
+//! types in this module parse the responses of the most commonly used info commands; the raw
+//! responses are available through `Client::info`.
+
+#![allow(clippy::missing_errors_doc)]
+
+pub use self::bin_info::BinInfo;
+pub use self::build_info::BuildInfo;
+pub use self::config_change::{ConfigChange, ConfigResult};
+pub use self::job_info::JobInfo;
+pub use self::namespace_info::{NamespaceConfig, NamespaceInfo};
+pub use self::node_statistics::NodeStatistics;
+pub use self::set_config::SetConfig;
+pub use self::set_info::SetInfo;
+
+mod bin_info;
+mod build_info;
+pub(crate) mod config_change;
+mod job_info;
+mod namespace_info;
+mod node_statistics;
+mod set_config;
+mod set_info;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::errors::{ErrorKind, Result};
+
+// Splits an info response of the form `key=value<sep>key=value` into a map. Entries without a
+// `=` are stored with an empty value.
+pub(crate) fn parse_pairs(response: &str, separator: char) -> HashMap<String, String> {
+    response
+        .split(separator)
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut kv = pair.splitn(2, '=');
+            let key = kv.next().unwrap_or_default();
+            let val = kv.next().unwrap_or_default();
+            (key.to_string(), val.to_string())
+        })
+        .collect()
+}
+
+// Returns an error if the info response indicates that the command failed. Servers respond with
+// either `ERROR:<code>:<message>` or `FAIL:<code>:<message>`.
+pub(crate) fn check_response(command: &str, response: &str) -> Result<()> {
+    if response.starts_with("ERROR") || response.starts_with("FAIL") {
+        bail!(ErrorKind::BadResponse(format!(
+            "Info command `{}` failed: {}",
+            command, response
+        )));
+    }
+    Ok(())
+}
+
+// Returns the value of the first of the given keys that is present in the map and can be parsed
+// into the requested type. Several statistics have been renamed between server versions.
+pub(crate) fn value_of<T: FromStr>(values: &HashMap<String, String>, keys: &[&str]) -> Option<T> {
+    keys.iter()
+        .filter_map(|key| values.get(*key))
+        .find_map(|val| val.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_response, parse_pairs, value_of};
+
+    #[test]
+    fn parse_pairs_semicolon() {
+        let map = parse_pairs("objects=10;stop_writes=false;empty=;flag", ';');
+        assert_eq!(map.len(), 4);
+        assert_eq!(map["objects"], "10");
+        assert_eq!(map["stop_writes"], "false");
+        assert_eq!(map["empty"], "");
+        assert_eq!(map["flag"], "");
+    }
+
+    #[test]
+    fn parse_pairs_trailing_separator() {
+        let map = parse_pairs("ns=test:set=demo:objects=1:", ':');
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["set"], "demo");
+    }
+
+    #[test]
+    fn value_of_fallback_keys() {
+        let map = parse_pairs("n_objects=5;x=abc", ';');
+        assert_eq!(value_of::<u64>(&map, &["objects", "n_objects"]), Some(5));
+        assert_eq!(value_of::<u64>(&map, &["x"]), None);
+        assert_eq!(value_of::<u64>(&map, &["missing"]), None);
+    }
+
+    #[test]
+    fn check_response_errors() {
+        assert!(check_response("build", "5.6.0.3").is_ok());
+        assert!(check_response("cluster-stable", "ERROR::unstable-cluster").is_err());
+        assert!(check_response("sets/foo", "FAIL:2:namespace not found").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::info::{parse_pairs, value_of};
+
+/// Namespace statistics as returned by the `namespace/<ns>` info command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceInfo {
+    /// Name of the namespace.
+    pub name: String,
+
+    /// Number of master and replica records in the namespace on this node.
+    pub objects: u64,
+
+    /// Number of tombstones in the namespace on this node.
+    pub tombstones: u64,
+
+    /// Effective replication factor of the namespace.
+    pub replication_factor: u64,
+
+    /// Memory used by the namespace on this node, in bytes.
+    pub memory_used_bytes: u64,
+
+    /// Storage device space used by the namespace on this node, in bytes.
+    pub device_used_bytes: u64,
+
+    /// Whether the namespace is currently refusing writes on this node.
+    pub stop_writes: bool,
+
+    /// All statistics returned by the server, including the ones listed above.
+    pub values: HashMap<String, String>,
+}
+
+impl NamespaceInfo {
+    /// Parse the response of the `namespace/<ns>` info command.
+    pub fn parse(name: &str, response: &str) -> Self {
+        let values = parse_pairs(response, ';');
+        NamespaceInfo {
+            name: name.to_string(),
+            objects: value_of(&values, &["objects"]).unwrap_or_default(),
+            tombstones: value_of(&values, &["tombstones"]).unwrap_or_default(),
+            replication_factor: value_of(
+                &values,
+                &["effective_replication_factor", "replication-factor"],
+            )
+            .unwrap_or_default(),
+            memory_used_bytes: value_of(&values, &["memory_used_bytes"]).unwrap_or_default(),
+            device_used_bytes: value_of(&values, &["device_used_bytes"]).unwrap_or_default(),
+            stop_writes: value_of(&values, &["stop_writes"]).unwrap_or_default(),
+            values,
+        }
+    }
+}
+
+/// Namespace configuration as returned by the `get-config:context=namespace;id=<ns>` info
+/// command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceConfig {
+    /// Name of the namespace.
+    pub name: String,
+
+    /// Configured replication factor.
+    pub replication_factor: u64,
+
+    /// Default time-to-live for records in the namespace, in seconds. Zero means records never
+    /// expire.
+    pub default_ttl: u64,
+
+    /// Maximum amount of memory for the namespace, in bytes.
+    pub memory_size: u64,
+
+    /// Interval in seconds at which the namespace supervisor expires and evicts records.
+    pub nsup_period: u64,
+
+    /// All configuration values returned by the server, including the ones listed above.
+    pub values: HashMap<String, String>,
+}
+
+impl NamespaceConfig {
+    /// Parse the response of the `get-config:context=namespace;id=<ns>` info command.
+    pub fn parse(name: &str, response: &str) -> Self {
+        let values = parse_pairs(response, ';');
+        NamespaceConfig {
+            name: name.to_string(),
+            replication_factor: value_of(&values, &["replication-factor"]).unwrap_or_default(),
+            default_ttl: value_of(&values, &["default-ttl"]).unwrap_or_default(),
+            memory_size: value_of(&values, &["memory-size"]).unwrap_or_default(),
+            nsup_period: value_of(&values, &["nsup-period"]).unwrap_or_default(),
+            values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NamespaceConfig, NamespaceInfo};
+
+    #[test]
+    fn parse_namespace_info() {
+        let response = "objects=42;tombstones=1;effective_replication_factor=2;\
+                        memory_used_bytes=1024;device_used_bytes=2048;stop_writes=false";
+        let info = NamespaceInfo::parse("test", response);
+        assert_eq!(info.name, "test");
+        assert_eq!(info.objects, 42);
+        assert_eq!(info.tombstones, 1);
+        assert_eq!(info.replication_factor, 2);
+        assert_eq!(info.memory_used_bytes, 1024);
+        assert_eq!(info.device_used_bytes, 2048);
+        assert!(!info.stop_writes);
+        assert_eq!(info.values.len(), 6);
+    }
+
+    #[test]
+    fn parse_namespace_config() {
+        let response = "replication-factor=2;memory-size=4294967296;default-ttl=2592000;\
+                        nsup-period=120;storage-engine=memory";
+        let config = NamespaceConfig::parse("test", response);
+        assert_eq!(config.replication_factor, 2);
+        assert_eq!(config.memory_size, 4_294_967_296);
+        assert_eq!(config.default_ttl, 2_592_000);
+        assert_eq!(config.nsup_period, 120);
+        assert_eq!(config.values["storage-engine"], "memory");
+    }
+}
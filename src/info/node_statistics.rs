@@ -0,0 +1,74 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::info::{parse_pairs, value_of};
+
+/// Node-wide statistics as returned by the `statistics` info command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeStatistics {
+    /// Number of nodes in the cluster, as seen by this node.
+    pub cluster_size: u64,
+
+    /// Key of the current cluster state, as seen by this node.
+    pub cluster_key: String,
+
+    /// Whether the cluster integrity check of this node succeeded.
+    pub cluster_integrity: bool,
+
+    /// Time since the node was started, in seconds.
+    pub uptime: u64,
+
+    /// Number of active client connections to this node.
+    pub client_connections: u64,
+
+    /// All statistics returned by the server, including the ones listed above.
+    pub values: HashMap<String, String>,
+}
+
+impl NodeStatistics {
+    /// Parse the response of the `statistics` info command.
+    pub fn parse(response: &str) -> Self {
+        let values = parse_pairs(response, ';');
+        NodeStatistics {
+            cluster_size: value_of(&values, &["cluster_size"]).unwrap_or_default(),
+            cluster_key: value_of(&values, &["cluster_key"]).unwrap_or_default(),
+            cluster_integrity: value_of(&values, &["cluster_integrity"]).unwrap_or_default(),
+            uptime: value_of(&values, &["uptime"]).unwrap_or_default(),
+            client_connections: value_of(&values, &["client_connections"]).unwrap_or_default(),
+            values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NodeStatistics;
+
+    #[test]
+    fn parse_statistics() {
+        let stats = NodeStatistics::parse(
+            "cluster_size=3;cluster_key=A1B2C3D4E5F6;cluster_integrity=true;uptime=3600;\
+             client_connections=12;heap_efficiency_pct=100",
+        );
+        assert_eq!(stats.cluster_size, 3);
+        assert_eq!(stats.cluster_key, "A1B2C3D4E5F6");
+        assert!(stats.cluster_integrity);
+        assert_eq!(stats.uptime, 3600);
+        assert_eq!(stats.client_connections, 12);
+        assert_eq!(stats.values["heap_efficiency_pct"], "100");
+    }
+}
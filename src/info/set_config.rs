@@ -0,0 +1,83 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+/// Set-level configuration changes, applied to all cluster nodes through
+/// `Client::configure_set`. Only the settings that are not `None` are changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetConfig {
+    /// Protect the records in the set from eviction.
+    pub disable_eviction: Option<bool>,
+
+    /// Default time-to-live for records in the set, in seconds. Overrides the namespace's
+    /// `default-ttl`. Requires server version 5.6 or later.
+    pub default_ttl: Option<u32>,
+
+    /// Number of objects at which writes to the set will be refused. Zero means no limit.
+    pub stop_writes_count: Option<u64>,
+}
+
+impl SetConfig {
+    /// Create a new, empty set configuration change.
+    pub fn new() -> Self {
+        SetConfig::default()
+    }
+
+    /// Build the `set-config` info commands for this configuration change. The server accepts
+    /// only a single parameter per command.
+    pub(crate) fn commands(&self, namespace: &str, set_name: &str) -> Vec<String> {
+        let mut params = vec![];
+        if let Some(disable_eviction) = self.disable_eviction {
+            params.push(format!("disable-eviction={}", disable_eviction));
+        }
+        if let Some(default_ttl) = self.default_ttl {
+            params.push(format!("default-ttl={}", default_ttl));
+        }
+        if let Some(stop_writes_count) = self.stop_writes_count {
+            params.push(format!("stop-writes-count={}", stop_writes_count));
+        }
+
+        params
+            .into_iter()
+            .map(|param| {
+                format!(
+                    "set-config:context=namespace;id={};set={};{}",
+                    namespace, set_name, param
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SetConfig;
+
+    #[test]
+    fn commands() {
+        let config = SetConfig {
+            disable_eviction: Some(true),
+            default_ttl: None,
+            stop_writes_count: Some(5000),
+        };
+        assert_eq!(
+            config.commands("test", "demo"),
+            vec![
+                "set-config:context=namespace;id=test;set=demo;disable-eviction=true",
+                "set-config:context=namespace;id=test;set=demo;stop-writes-count=5000",
+            ]
+        );
+        assert!(SetConfig::new().commands("test", "demo").is_empty());
+    }
+}
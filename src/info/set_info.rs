@@ -0,0 +1,179 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::info::{parse_pairs, value_of};
+
+/// Set statistics and set-level configuration as returned by the `sets/<ns>` info command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetInfo {
+    /// Namespace the set belongs to.
+    pub namespace: String,
+
+    /// Name of the set.
+    pub set_name: String,
+
+    /// Number of master and replica records in the set.
+    pub objects: u64,
+
+    /// Number of tombstones in the set.
+    pub tombstones: u64,
+
+    /// Memory used by the records of the set, in bytes.
+    pub memory_data_bytes: u64,
+
+    /// Storage device space used by the records of the set, in bytes.
+    pub device_data_bytes: u64,
+
+    /// Last update time cut-off of the most recent truncation of the set, in milliseconds as
+    /// reported by the server. Records last updated before it are deleted. Zero if the set has
+    /// never been truncated.
+    pub truncate_lut: u64,
+
+    /// Number of objects at which writes to the set will be refused. Zero means no limit.
+    pub stop_writes_count: u64,
+
+    /// Whether records in the set are protected from eviction.
+    pub disable_eviction: bool,
+
+    /// All values returned by the server for the set, including the ones listed above.
+    pub values: HashMap<String, String>,
+}
+
+impl SetInfo {
+    /// Parse a single set entry of the `sets/<ns>` info command response, i.e. a list of
+    /// `key=value` pairs separated by colons.
+    pub fn parse(entry: &str) -> Self {
+        let values = parse_pairs(entry, ':');
+        SetInfo {
+            namespace: value_of(&values, &["ns", "ns_name"]).unwrap_or_default(),
+            set_name: value_of(&values, &["set", "set_name"]).unwrap_or_default(),
+            objects: value_of(&values, &["objects", "n_objects"]).unwrap_or_default(),
+            tombstones: value_of(&values, &["tombstones"]).unwrap_or_default(),
+            memory_data_bytes: value_of(&values, &["memory_data_bytes", "n-bytes-memory"])
+                .unwrap_or_default(),
+            device_data_bytes: value_of(&values, &["device_data_bytes"]).unwrap_or_default(),
+            truncate_lut: value_of(&values, &["truncate_lut"]).unwrap_or_default(),
+            stop_writes_count: value_of(&values, &["stop-writes-count", "set-stop-writes-count"])
+                .unwrap_or_default(),
+            disable_eviction: value_of(&values, &["disable-eviction", "set-disable-eviction"])
+                .unwrap_or_default(),
+            values,
+        }
+    }
+
+    /// Parse the response of the `sets/<ns>` info command into a list of sets.
+    pub fn parse_list(response: &str) -> Vec<Self> {
+        response
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(SetInfo::parse)
+            .collect()
+    }
+
+    /// Combine the per-node set statistics of a namespace into cluster-wide statistics. Object,
+    /// tombstone, memory and device usage counts are summed up across all nodes. Since every
+    /// record is stored on `replication_factor` nodes, the object and tombstone counts are then
+    /// divided by the replication factor to yield the number of unique records. Set-level
+    /// configuration and the raw values are taken from the first node reporting the set.
+    pub fn aggregate(per_node: Vec<Vec<SetInfo>>, replication_factor: u64) -> Vec<Self> {
+        let mut sets: Vec<SetInfo> = vec![];
+        for set in per_node.into_iter().flatten() {
+            match sets.iter_mut().find(|s| s.set_name == set.set_name) {
+                Some(existing) => {
+                    existing.objects += set.objects;
+                    existing.tombstones += set.tombstones;
+                    existing.memory_data_bytes += set.memory_data_bytes;
+                    existing.device_data_bytes += set.device_data_bytes;
+                }
+                None => sets.push(set),
+            }
+        }
+
+        let replication_factor = replication_factor.max(1);
+        for set in &mut sets {
+            set.objects /= replication_factor;
+            set.tombstones /= replication_factor;
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SetInfo;
+
+    #[test]
+    fn parse_sets() {
+        let response = "ns=test:set=demo:objects=10:tombstones=0:memory_data_bytes=640:\
+                        device_data_bytes=0:truncate_lut=0:stop-writes-count=0:\
+                        disable-eviction=false;ns=test:set=users:objects=3:tombstones=1:\
+                        memory_data_bytes=96:device_data_bytes=0:truncate_lut=446164645123:\
+                        stop-writes-count=1000:disable-eviction=true;";
+        let sets = SetInfo::parse_list(response);
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].namespace, "test");
+        assert_eq!(sets[0].set_name, "demo");
+        assert_eq!(sets[0].objects, 10);
+        assert_eq!(sets[0].memory_data_bytes, 640);
+        assert!(!sets[0].disable_eviction);
+        assert_eq!(sets[1].set_name, "users");
+        assert_eq!(sets[1].tombstones, 1);
+        assert_eq!(sets[1].truncate_lut, 446_164_645_123);
+        assert_eq!(sets[1].stop_writes_count, 1000);
+        assert!(sets[1].disable_eviction);
+    }
+
+    #[test]
+    fn parse_legacy_set_names() {
+        let sets = SetInfo::parse_list(
+            "ns_name=test:set_name=demo:n_objects=7:set-stop-writes-count=5:\
+             set-disable-eviction=true",
+        );
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].set_name, "demo");
+        assert_eq!(sets[0].objects, 7);
+        assert_eq!(sets[0].stop_writes_count, 5);
+        assert!(sets[0].disable_eviction);
+    }
+
+    #[test]
+    fn aggregate_sets() {
+        let node1 = SetInfo::parse_list(
+            "ns=test:set=demo:objects=6:tombstones=2:memory_data_bytes=600:\
+             stop-writes-count=100;ns=test:set=other:objects=1",
+        );
+        let node2 = SetInfo::parse_list(
+            "ns=test:set=demo:objects=4:tombstones=0:memory_data_bytes=400:\
+             stop-writes-count=100;ns=test:set=other:objects=1",
+        );
+        let sets = SetInfo::aggregate(vec![node1, node2], 2);
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].set_name, "demo");
+        assert_eq!(sets[0].objects, 5);
+        assert_eq!(sets[0].tombstones, 1);
+        assert_eq!(sets[0].memory_data_bytes, 1000);
+        assert_eq!(sets[0].stop_writes_count, 100);
+        assert_eq!(sets[1].set_name, "other");
+        assert_eq!(sets[1].objects, 1);
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!(SetInfo::parse_list("").is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Hooks to observe or modify every command executed by the client.
+//!
+//! Interceptors registered on the `ClientPolicy` are invoked before and after every command sent
+//! to a node. Batch, scan and query operations run the hooks once for every node they are sent
+//! to.
+//!
+//! # Examples
+//!
+//! Log slow commands and restrict all commands to the records of a single tenant.
+//!
+//! ```rust
+//! # use aerospike::*;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use aerospike::expressions::{and, eq, int_bin, int_val};
+//! use aerospike::interceptor::{CommandContext, Interceptor};
+//!
+//! #[derive(Debug)]
+//! struct TenantInterceptor {
+//!     tenant: i64,
+//! }
+//!
+//! impl Interceptor for TenantInterceptor {
+//!     fn before_command(&self, ctx: &mut CommandContext) -> Result<()> {
+//!         let tenant = eq(int_bin("tenant".to_string()), int_val(self.tenant));
+//!         let filter = match ctx.filter_expression() {
+//!             Some(filter) => and(vec![filter.clone(), tenant]),
+//!             None => tenant,
+//!         };
+//!         ctx.set_filter_expression(filter);
+//!         Ok(())
+//!     }
+//!
+//!     fn after_command(&self, ctx: &CommandContext, _result: &Result<()>) {
+//!         if ctx.elapsed() > Duration::from_millis(100) {
+//!             println!("slow {} command on keys {:?}", ctx.command_type(), ctx.keys());
+//!         }
+//!     }
+//! }
+//!
+//! let mut policy = ClientPolicy::default();
+//! policy.interceptors.push(Arc::new(TenantInterceptor { tenant: 42 }));
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::cluster::Node;
+use crate::commands::Command;
+use crate::errors::Result;
+use crate::expressions::FilterExpression;
+use crate::metrics::CommandType;
+use crate::policy::Policy;
+use crate::Key;
+
+/// Hooks invoked around every command sent to a node.
+pub trait Interceptor: fmt::Debug + Send + Sync {
+    /// Called before the command is sent. Returning an error fails the command with that error
+    /// without sending it; `after_command` is still called.
+    fn before_command(&self, _ctx: &mut CommandContext) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called after the command completed, successfully or not, including all retries.
+    fn after_command(&self, _ctx: &CommandContext, _result: &Result<()>) {}
+}
+
+/// Information about a command, passed to the `Interceptor` hooks.
+pub struct CommandContext<'a> {
+    command_type: CommandType,
+    policy: &'a dyn Policy,
+    keys: Vec<&'a Key>,
+    node: Option<Arc<Node>>,
+    start: Instant,
+    filter_expression: Option<FilterExpression>,
+    filter_modified: bool,
+}
+
+impl<'a> CommandContext<'a> {
+    fn new(
+        policy: &'a dyn Policy,
+        cmd: &'a dyn Command,
+        filter_expression: Option<&FilterExpression>,
+        node: Option<Arc<Node>>,
+        start: Instant,
+    ) -> Self {
+        CommandContext {
+            command_type: cmd.command_type(),
+            policy,
+            keys: cmd.keys(),
+            node,
+            start,
+            filter_expression: filter_expression.cloned(),
+            filter_modified: false,
+        }
+    }
+
+    /// Type of the command.
+    pub const fn command_type(&self) -> CommandType {
+        self.command_type
+    }
+
+    /// Policy the command is executed with.
+    pub fn policy(&self) -> &dyn Policy {
+        self.policy
+    }
+
+    /// Keys of the records the command operates on. Empty for scans and queries.
+    pub fn keys(&self) -> &[&Key] {
+        &self.keys
+    }
+
+    /// Node the command is sent to. Before the command, this is the node selected for the first
+    /// attempt; after the command, the node of the last attempt. `None` if no node could be
+    /// selected.
+    pub fn node(&self) -> Option<&Arc<Node>> {
+        self.node.as_ref()
+    }
+
+    /// Time elapsed since the command started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Filter expression the command is sent with; initially the one of the policy.
+    pub fn filter_expression(&self) -> Option<&FilterExpression> {
+        self.filter_expression.as_ref()
+    }
+
+    /// Replaces the filter expression the command is sent with. Only takes effect in
+    /// `before_command`.
+    pub fn set_filter_expression(&mut self, filter: FilterExpression) {
+        self.filter_expression = Some(filter);
+        self.filter_modified = true;
+    }
+}
+
+impl<'a> fmt::Debug for CommandContext<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CommandContext")
+            .field("command_type", &self.command_type)
+            .field("keys", &self.keys)
+            .field("node", &self.node.as_ref().map(|node| node.name()))
+            .field("filter_expression", &self.filter_expression)
+            .finish()
+    }
+}
+
+// Runs the `before_command` hooks of the interceptors, stopping at the first error. Returns the
+// filter expression set by the interceptors, if any.
+pub(crate) fn before_command(
+    interceptors: &[Arc<dyn Interceptor>],
+    policy: &dyn Policy,
+    cmd: &dyn Command,
+    start: Instant,
+) -> Result<Option<FilterExpression>> {
+    if interceptors.is_empty() {
+        return Ok(None);
+    }
+    let node = cmd.get_node().ok();
+    let mut ctx = CommandContext::new(policy, cmd, cmd.filter_expression(), node, start);
+    for interceptor in interceptors {
+        interceptor.before_command(&mut ctx)?;
+    }
+    if ctx.filter_modified {
+        Ok(ctx.filter_expression)
+    } else {
+        Ok(None)
+    }
+}
+
+// Runs the `after_command` hooks of the interceptors.
+pub(crate) fn after_command(
+    interceptors: &[Arc<dyn Interceptor>],
+    policy: &dyn Policy,
+    cmd: &dyn Command,
+    filter_expression: Option<&FilterExpression>,
+    node: Option<Arc<Node>>,
+    start: Instant,
+    result: &Result<()>,
+) {
+    if interceptors.is_empty() {
+        return;
+    }
+    let filter_expression = filter_expression.or_else(|| cmd.filter_expression());
+    let ctx = CommandContext::new(policy, cmd, filter_expression, node, start);
+    for interceptor in interceptors {
+        interceptor.after_command(&ctx, result);
+    }
+}
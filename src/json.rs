@@ -0,0 +1,183 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Conversion between `serde_json::Value` and `Value`. JSON objects are converted to maps with
+//! string keys, arrays to lists. JSON numbers are converted to `Value::Int` if they fit into an
+//! `i64`, to `Value::UInt` if they fit into an `u64` and to `Value::Float` otherwise.
+//!
+//! Converting a `Value` to JSON fails for maps with non-string keys, blobs, HLLs and non-finite
+//! floats, which have no JSON representation. GeoJSON values are converted to the JSON object
+//! they contain.
+
+use std::convert::TryFrom;
+
+use serde_json::{Map, Number};
+
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::mapping::{invalid_value, FromValue, ToValue};
+use crate::Value;
+
+impl From<serde_json::Value> for Value {
+    fn from(val: serde_json::Value) -> Value {
+        Value::from(&val)
+    }
+}
+
+impl<'a> From<&'a serde_json::Value> for Value {
+    fn from(val: &'a serde_json::Value) -> Value {
+        match *val {
+            serde_json::Value::Null => Value::Nil,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(ref n) => {
+                if let Some(i) = n.as_i64() {
+                    Value::Int(i)
+                } else if let Some(u) = n.as_u64() {
+                    Value::UInt(u)
+                } else {
+                    Value::from(n.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            serde_json::Value::String(ref s) => Value::String(s.clone()),
+            serde_json::Value::Array(ref a) => Value::List(a.iter().map(Value::from).collect()),
+            serde_json::Value::Object(ref o) => Value::HashMap(
+                o.iter()
+                    .map(|(k, v)| (Value::String(k.clone()), Value::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl TryFrom<Value> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<serde_json::Value> {
+        serde_json::Value::try_from(&val)
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<serde_json::Value> {
+        let json = match *val {
+            Value::Nil => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(b),
+            Value::Int(i) => serde_json::Value::Number(Number::from(i)),
+            Value::UInt(u) => serde_json::Value::Number(Number::from(u)),
+            Value::Float(ref f) => match Number::from_f64(f.as_f64()) {
+                Some(n) => serde_json::Value::Number(n),
+                None => bail!(ErrorKind::InvalidValue(format!(
+                    "float {} has no JSON representation",
+                    f
+                ))),
+            },
+            Value::String(ref s) => serde_json::Value::String(s.clone()),
+            Value::GeoJSON(ref s) => serde_json::from_str(s)
+                .map_err(|err| ErrorKind::InvalidValue(format!("invalid GeoJSON: {}", err)))?,
+            Value::List(ref l) => serde_json::Value::Array(
+                l.iter()
+                    .map(serde_json::Value::try_from)
+                    .collect::<Result<_>>()?,
+            ),
+            Value::HashMap(ref m) => json_object(m.iter())?,
+            Value::OrderedMap(ref m) => json_object(m.iter().map(|(k, v)| (k, v)))?,
+            ref val => return Err(invalid_value("JSON compatible value", val)),
+        };
+        Ok(json)
+    }
+}
+
+fn json_object<'a, I>(entries: I) -> Result<serde_json::Value>
+where
+    I: Iterator<Item = (&'a Value, &'a Value)>,
+{
+    let mut map = Map::new();
+    for (key, val) in entries {
+        let key = match *key {
+            Value::String(ref s) => s.clone(),
+            ref key => return Err(invalid_value("string map key", key)),
+        };
+        let val = serde_json::Value::try_from(val)
+            .chain_err(|| format!("Error converting map entry '{}'", key))?;
+        map.insert(key, val);
+    }
+    Ok(serde_json::Value::Object(map))
+}
+
+impl ToValue for serde_json::Value {
+    fn to_value(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl FromValue for serde_json::Value {
+    fn from_value(value: &Value) -> Result<Self> {
+        serde_json::Value::try_from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use serde_json::json;
+
+    use crate::Value;
+
+    #[test]
+    fn from_json() {
+        let json = json!({
+            "name": "Jane",
+            "age": 42,
+            "score": 1.5,
+            "big": u64::MAX,
+            "tags": ["a", null, true],
+        });
+        assert_eq!(
+            Value::from(json),
+            as_map!(
+                "name" => "Jane",
+                "age" => 42,
+                "score" => 1.5,
+                "big" => Value::UInt(u64::MAX),
+                "tags" => as_list!("a", Value::Nil, true)
+            )
+        );
+    }
+
+    #[test]
+    fn to_json() {
+        let json = json!({
+            "name": "Jane",
+            "age": -42,
+            "score": 1.5,
+            "big": u64::MAX,
+            "nested": {"tags": ["a", null, true]},
+        });
+        let value = Value::from(&json);
+        assert_eq!(serde_json::Value::try_from(value).unwrap(), json);
+
+        let geo = as_geo!(r#"{"type":"Point","coordinates":[1.5,2.5]}"#);
+        assert_eq!(
+            serde_json::Value::try_from(geo).unwrap(),
+            json!({"type": "Point", "coordinates": [1.5, 2.5]})
+        );
+
+        assert!(serde_json::Value::try_from(as_map!(1 => 2)).is_err());
+        assert!(serde_json::Value::try_from(as_blob!(vec![1u8])).is_err());
+        assert!(serde_json::Value::try_from(Value::from(f64::NAN)).is_err());
+    }
+}
@@ -157,6 +157,7 @@ pub use commands::particle_type::ParticleType;
 pub use errors::{Error, ErrorKind, Result};
 pub use expressions::regex_flag::RegexFlag;
 pub use key::Key;
+pub use mapping::{AerospikeRecord, FromValue, ToValue};
 pub use net::Host;
 pub use operations::{MapPolicy, MapReturnType, MapWriteMode};
 pub use policy::{
@@ -164,13 +165,17 @@ pub use policy::{
     GenerationPolicy, Policy, Priority, QueryPolicy, ReadPolicy, RecordExistsAction, ScanPolicy,
     WritePolicy,
 };
-pub use query::{CollectionIndexType, IndexType, Recordset, Statement, UDFLang};
+pub use query::{CollectionIndexType, ControlFlow, IndexType, Recordset, Statement, UDFLang};
 pub use record::Record;
+pub use record_ref::{BinsRef, RecordRef, ValueRef};
 pub use result_code::ResultCode;
-pub use task::{IndexTask, RegisterTask, Task};
+pub use task::{IndexTask, RegisterTask, Task, TruncateTask};
 pub use user::User;
 pub use value::{FloatValue, Value};
 
+#[cfg(feature = "derive")]
+pub use aerospike_derive::AerospikeRecord;
+
 #[macro_use]
 pub mod errors;
 #[macro_use]
@@ -184,14 +189,23 @@ mod client;
 mod cluster;
 mod commands;
 pub mod expressions;
+pub mod info;
+pub mod interceptor;
+#[cfg(feature = "json")]
+mod json;
+pub mod mapping;
+pub mod metrics;
 mod msgpack;
 mod net;
 pub mod operations;
 pub mod policy;
 pub mod query;
 mod record;
+mod record_ref;
 mod result_code;
 pub mod task;
+mod trace;
+pub mod transport;
 mod user;
 
 #[cfg(test)]
@@ -0,0 +1,430 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Traits for mapping Rust types to Aerospike records and values.
+//!
+//! The `AerospikeRecord` trait converts a struct to a list of bins for writing and back from a
+//! record returned by the server. Instead of implementing it by hand, enable the `derive` feature
+//! and use `#[derive(AerospikeRecord)]`. The derived implementation supports the following field
+//! attributes:
+//!
+//! * `#[aerospike(rename = "name")]` - use a different bin name (or map key, for nested structs).
+//! * `#[aerospike(skip)]` - do not write the field; when reading, the field is set to its
+//!   `Default` value.
+//!
+//! Fields of type `Option<T>` are written as `nil` bins if they are `None`, which removes the bin
+//! from the record, and are read as `None` if the bin does not exist. `Vec<T>` and `HashMap<K, V>`
+//! fields are stored as lists and maps. Fields whose type derives `AerospikeRecord` themselves are
+//! stored as maps keyed by field name.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use aerospike::*;
+//!
+//! #[derive(AerospikeRecord)]
+//! struct User {
+//!     name: String,
+//!     #[aerospike(rename = "mail")]
+//!     email: Option<String>,
+//!     tags: Vec<String>,
+//!     #[aerospike(skip)]
+//!     cached: bool,
+//! }
+//!
+//! let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+//! let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+//! let key = as_key!("test", "users", 1);
+//! let user = User { name: "Jane".into(), email: None, tags: vec![], cached: false };
+//! client.put_from(&WritePolicy::default(), &key, &user).unwrap();
+//! let user: User = client.get_as(&ReadPolicy::default(), &key).unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hash};
+
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::{Bin, FloatValue, Record, Value};
+
+/// Conversion of a Rust type to a record bin or map value.
+pub trait ToValue {
+    /// Convert the value to an Aerospike value.
+    fn to_value(&self) -> Value;
+}
+
+/// Conversion of a record bin or map value to a Rust type.
+pub trait FromValue: Sized {
+    /// Convert an Aerospike value to the Rust type. Missing bins and map entries are passed as
+    /// `Value::Nil`.
+    fn from_value(value: &Value) -> Result<Self>;
+}
+
+/// Mapping of a Rust struct to an Aerospike record.
+pub trait AerospikeRecord: Sized {
+    /// Names of all bins the struct is mapped to.
+    fn bin_names() -> Vec<&'static str>;
+
+    /// Convert the struct to a list of bins, e.g. to write it using `Client::put`.
+    fn to_bins(&self) -> Vec<Bin<'static>>;
+
+    /// Construct the struct from the bins of a record.
+    fn from_record(record: &Record) -> Result<Self>;
+}
+
+/// Read a bin from a record and convert it to the given type. Used by the derived
+/// `AerospikeRecord` implementations.
+pub fn bin_value<T: FromValue>(record: &Record, name: &str) -> Result<T> {
+    let value = record.bins.get(name).unwrap_or(&Value::Nil);
+    T::from_value(value).chain_err(|| format!("Error reading bin '{}'", name))
+}
+
+/// Read an entry with a string key from a map value and convert it to the given type. Used by the
+/// derived `FromValue` implementations of nested structs.
+pub fn map_value<T: FromValue>(map: &Value, key: &str) -> Result<T> {
+    let value = match *map {
+        Value::HashMap(ref entries) => entries.get(&Value::from(key)),
+        Value::OrderedMap(ref entries) => entries
+            .iter()
+            .find(|(k, _)| *k == Value::from(key))
+            .map(|(_, v)| v),
+        ref value => return Err(invalid_value("map", value)),
+    };
+    T::from_value(value.unwrap_or(&Value::Nil))
+        .chain_err(|| format!("Error reading map entry '{}'", key))
+}
+
+const fn type_name(value: &Value) -> &'static str {
+    match *value {
+        Value::Nil => "nil",
+        Value::Bool(_) => "bool",
+        Value::Int(_) | Value::UInt(_) => "integer",
+        Value::Float(_) => "float",
+        Value::String(_) => "string",
+        Value::Blob(_) => "blob",
+        Value::List(_) => "list",
+        Value::HashMap(_) | Value::OrderedMap(_) => "map",
+        Value::GeoJSON(_) => "geojson",
+        Value::HLL(_) => "hll",
+        Value::Wildcard => "wildcard",
+        Value::Infinity => "infinity",
+    }
+}
+
+pub(crate) fn invalid_value(expected: &str, value: &Value) -> Error {
+    ErrorKind::InvalidValue(format!("expected {}, found {}", expected, type_name(value))).into()
+}
+
+impl ToValue for Value {
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self> {
+        match *value {
+            Value::Bool(val) => Ok(val),
+            // Servers without native boolean support store booleans as integers.
+            Value::Int(val) => Ok(val != 0),
+            ref value => Err(invalid_value("bool", value)),
+        }
+    }
+}
+
+macro_rules! int_mapping {
+    ($($t:ty),*) => {
+        $(
+            impl ToValue for $t {
+                fn to_value(&self) -> Value {
+                    #[allow(clippy::cast_lossless)]
+                    match i64::try_from(*self) {
+                        Ok(val) => Value::Int(val),
+                        Err(_) => Value::UInt(*self as u64),
+                    }
+                }
+            }
+
+            impl FromValue for $t {
+                fn from_value(value: &Value) -> Result<Self> {
+                    let result = match *value {
+                        Value::Int(val) => <$t>::try_from(val).ok(),
+                        Value::UInt(val) => <$t>::try_from(val).ok(),
+                        ref value => return Err(invalid_value(stringify!($t), value)),
+                    };
+                    result.ok_or_else(|| {
+                        ErrorKind::InvalidValue(format!(
+                            "integer {} out of range for {}",
+                            value,
+                            stringify!($t)
+                        ))
+                        .into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+int_mapping!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ToValue for f32 {
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+impl FromValue for f32 {
+    fn from_value(value: &Value) -> Result<Self> {
+        match *value {
+            Value::Float(FloatValue::F32(val)) => Ok(f32::from_bits(val)),
+            Value::Float(FloatValue::F64(val)) => Ok(f64::from_bits(val) as f32),
+            ref value => Err(invalid_value("f32", value)),
+        }
+    }
+}
+
+impl ToValue for f64 {
+    fn to_value(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Result<Self> {
+        match *value {
+            Value::Float(FloatValue::F32(val)) => Ok(f64::from(f32::from_bits(val))),
+            Value::Float(FloatValue::F64(val)) => Ok(f64::from_bits(val)),
+            ref value => Err(invalid_value("f64", value)),
+        }
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self> {
+        match *value {
+            Value::String(ref val) => Ok(val.clone()),
+            ref value => Err(invalid_value("string", value)),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for Option<T> {
+    fn to_value(&self) -> Value {
+        self.as_ref().map_or(Value::Nil, ToValue::to_value)
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self> {
+        match *value {
+            Value::Nil => Ok(None),
+            ref value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for Vec<T> {
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Result<Self> {
+        match *value {
+            Value::List(ref values) => values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    T::from_value(v).chain_err(|| format!("Error reading list element {}", i))
+                })
+                .collect(),
+            // Allows reading blobs as `Vec<u8>`.
+            Value::Blob(ref bytes) | Value::HLL(ref bytes) => bytes
+                .iter()
+                .map(|b| T::from_value(&Value::from(*b)))
+                .collect(),
+            ref value => Err(invalid_value("list", value)),
+        }
+    }
+}
+
+impl<K: ToValue, V: ToValue, S> ToValue for HashMap<K, V, S> {
+    fn to_value(&self) -> Value {
+        Value::HashMap(
+            self.iter()
+                .map(|(k, v)| (k.to_value(), v.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V, S> FromValue for HashMap<K, V, S>
+where
+    K: FromValue + Eq + Hash,
+    V: FromValue,
+    S: BuildHasher + Default,
+{
+    fn from_value(value: &Value) -> Result<Self> {
+        let convert = |(k, v): (&Value, &Value)| -> Result<(K, V)> {
+            let key = K::from_value(k).chain_err(|| "Error reading map key")?;
+            let val = V::from_value(v).chain_err(|| format!("Error reading map entry {}", k))?;
+            Ok((key, val))
+        };
+        match *value {
+            Value::HashMap(ref entries) => entries.iter().map(convert).collect(),
+            Value::OrderedMap(ref entries) => {
+                entries.iter().map(|(k, v)| convert((k, v))).collect()
+            }
+            ref value => Err(invalid_value("map", value)),
+        }
+    }
+}
+
+macro_rules! tuple_mapping {
+    ($len:expr => $($t:ident $idx:tt),+) => {
+        impl<$($t: ToValue),+> ToValue for ($($t,)+) {
+            fn to_value(&self) -> Value {
+                Value::List(vec![$(self.$idx.to_value()),+])
+            }
+        }
+
+        impl<$($t: FromValue),+> FromValue for ($($t,)+) {
+            fn from_value(value: &Value) -> Result<Self> {
+                match *value {
+                    Value::List(ref values) if values.len() == $len => Ok(($(
+                        $t::from_value(&values[$idx])
+                            .chain_err(|| format!("Error reading tuple element {}", $idx))?,
+                    )+)),
+                    Value::List(ref values) => Err(ErrorKind::InvalidValue(format!(
+                        "expected list of {} elements, found list of {} elements",
+                        $len,
+                        values.len()
+                    ))
+                    .into()),
+                    ref value => Err(invalid_value("list", value)),
+                }
+            }
+        }
+    };
+}
+
+tuple_mapping!(1 => A 0);
+tuple_mapping!(2 => A 0, B 1);
+tuple_mapping!(3 => A 0, B 1, C 2);
+tuple_mapping!(4 => A 0, B 1, C 2, D 3);
+tuple_mapping!(5 => A 0, B 1, C 2, D 3, E 4);
+tuple_mapping!(6 => A 0, B 1, C 2, D 3, E 4, F 5);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{map_value, FromValue, ToValue};
+    use crate::Value;
+
+    #[test]
+    fn primitives() {
+        assert_eq!(42i32.to_value(), Value::Int(42));
+        assert_eq!(u64::max_value().to_value(), Value::UInt(u64::max_value()));
+        assert_eq!(i32::from_value(&Value::Int(42)).unwrap(), 42);
+        assert!(u8::from_value(&Value::Int(300)).is_err());
+        assert!(i64::from_value(&Value::from("42")).is_err());
+        assert!(bool::from_value(&Value::Int(1)).unwrap());
+        assert_eq!(f64::from_value(&Value::from(1.5)).unwrap(), 1.5);
+        assert_eq!(f64::from_value(&Value::from(1.5f32)).unwrap(), 1.5);
+        assert_eq!(f32::from_value(&Value::from(1.5f32)).unwrap(), 1.5);
+        assert_eq!(f32::from_value(&Value::from(1.5)).unwrap(), 1.5);
+        assert_eq!(String::from_value(&Value::from("a")).unwrap(), "a");
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(None::<i64>.to_value(), Value::Nil);
+        assert_eq!(Option::<i64>::from_value(&Value::Nil).unwrap(), None);
+        assert_eq!(Option::<i64>::from_value(&Value::Int(1)).unwrap(), Some(1));
+        assert!(i64::from_value(&Value::Nil).is_err());
+    }
+
+    #[test]
+    fn collections() {
+        let list = vec![1i64, 2, 3];
+        assert_eq!(list.to_value(), as_list!(1, 2, 3));
+        assert_eq!(Vec::<i64>::from_value(&list.to_value()).unwrap(), list);
+        assert!(Vec::<i64>::from_value(&as_list!(1, "a")).is_err());
+
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1i64);
+        assert_eq!(map.to_value(), as_map!("a" => 1));
+        let ordered = Value::OrderedMap(vec![(Value::from("a"), Value::Int(1))]);
+        assert_eq!(HashMap::<String, i64>::from_value(&ordered).unwrap(), map);
+        assert_eq!(map_value::<i64>(&ordered, "a").unwrap(), 1);
+        assert_eq!(map_value::<Option<i64>>(&ordered, "b").unwrap(), None);
+    }
+
+    #[test]
+    fn tuples() {
+        let tuple = (1i64, "a".to_string(), true);
+        assert_eq!(tuple.to_value(), as_list!(1, "a", true));
+        assert_eq!(
+            <(i64, String, bool)>::from_value(&as_list!(1, "a", true)).unwrap(),
+            tuple
+        );
+        assert!(<(i64, String)>::from_value(&as_list!(1, "a", true)).is_err());
+        assert!(<(i64, String)>::from_value(&as_list!("a", 1)).is_err());
+    }
+
+    #[test]
+    fn blobs() {
+        let blob = Value::Blob(vec![1, 2, 3]);
+        assert_eq!(Vec::<u8>::from_value(&blob).unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            Vec::<u8>::from_value(&as_list!(1, 2, 3)).unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn error_message() {
+        let err = Vec::<i64>::from_value(&as_list!(1, "a")).unwrap_err();
+        let messages: Vec<String> = err.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Error reading list element 1",
+                "Invalid value: expected i64, found string"
+            ]
+        );
+    }
+}
@@ -0,0 +1,439 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Client-side command metrics.
+//!
+//! The client reports the latency and outcome of every command to the `MetricsListener` set on
+//! the `ClientPolicy`. `LatencyMetrics` is a built-in listener that keeps a latency histogram
+//! and error counters per command type and node.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use aerospike::*;
+//! use std::sync::Arc;
+//! use aerospike::metrics::{CommandType, LatencyMetrics};
+//!
+//! let metrics = Arc::new(LatencyMetrics::new());
+//! let mut policy = ClientPolicy::default();
+//! policy.metrics_listener = Some(metrics.clone());
+//! # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+//! let client = Client::new(&policy, &hosts).unwrap();
+//!
+//! let key = as_key!("test", "test", 1);
+//! client.put(&WritePolicy::default(), &key, &[as_bin!("a", 1)]).unwrap();
+//! for (command_type, node, hist) in metrics.histograms() {
+//!     if command_type == CommandType::Write {
+//!         println!("{} writes on node {}, avg: {} μs", hist.count(), node, hist.avg());
+//!     }
+//! }
+//! ```
+
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod stats;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+
+use crate::cluster::Node;
+use crate::commands;
+use crate::errors::{Error, ErrorKind, Result};
+use crate::ResultCode;
+
+pub use self::stats::{ClientStats, NodeStats, PoolStats};
+
+/// Number of buckets of the latency histogram.
+///
+/// The upper bound of the first bucket is 1ms and doubles for each following bucket; the last
+/// bucket holds all larger latencies: "<1ms", "<2ms", "<4ms", "<8ms", "<16ms", ">=16ms".
+pub const HIST_BUCKETS: usize = 6;
+
+/// Type of a command, as reported to the `MetricsListener`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CommandType {
+    /// Single record read, including exists.
+    Read,
+    /// Single record write, including delete and touch.
+    Write,
+    /// Operate command.
+    Operate,
+    /// Batch read command to a single node.
+    Batch,
+    /// Scan of a single node.
+    Scan,
+    /// Query of a single node.
+    Query,
+    /// User-defined function executed on a single record.
+    Udf,
+}
+
+// All command types, in the order of their discriminants.
+const COMMAND_TYPES: [CommandType; 7] = [
+    CommandType::Read,
+    CommandType::Write,
+    CommandType::Operate,
+    CommandType::Batch,
+    CommandType::Scan,
+    CommandType::Query,
+    CommandType::Udf,
+];
+
+impl CommandType {
+    /// Returns the lower case name of the command type.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            CommandType::Read => "read",
+            CommandType::Write => "write",
+            CommandType::Operate => "operate",
+            CommandType::Batch => "batch",
+            CommandType::Scan => "scan",
+            CommandType::Query => "query",
+            CommandType::Udf => "udf",
+        }
+    }
+}
+
+impl fmt::Display for CommandType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Listener that receives the outcome of every command executed by the client.
+///
+/// Batch, scan and query commands are reported once per node they were sent to. The listener is
+/// called on the thread that executed the command and should return quickly.
+pub trait MetricsListener: fmt::Debug + Send + Sync {
+    /// Called after a command completed on a node. The latency covers all retries of the
+    /// command; `error` is set if the command failed. Commands that fail before a node could be
+    /// selected are not reported.
+    fn command_completed(
+        &self,
+        command_type: CommandType,
+        node: &str,
+        latency: Duration,
+        error: Option<&Error>,
+    );
+}
+
+/// Latency histogram with error and timeout counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Histogram {
+    buckets: [u64; HIST_BUCKETS],
+    min: u64,
+    max: u64,
+    sum: u64,
+    count: u64,
+    errors: u64,
+    timeouts: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram::new()
+    }
+}
+
+impl Histogram {
+    /// Create a new, empty histogram.
+    pub const fn new() -> Self {
+        Histogram {
+            buckets: [0; HIST_BUCKETS],
+            min: u64::MAX,
+            max: 0,
+            sum: 0,
+            count: 0,
+            errors: 0,
+            timeouts: 0,
+        }
+    }
+
+    /// Returns the upper bound of the histogram bucket with the given index, or `None` for the
+    /// last bucket, which has no upper bound.
+    pub const fn bucket_bound(index: usize) -> Option<Duration> {
+        if index + 1 < HIST_BUCKETS {
+            Some(Duration::from_millis(1 << index))
+        } else {
+            None
+        }
+    }
+
+    /// Number of commands per latency bucket.
+    pub const fn buckets(&self) -> &[u64; HIST_BUCKETS] {
+        &self.buckets
+    }
+
+    /// Minimum latency in microseconds; 0 if the histogram is empty.
+    pub const fn min(&self) -> u64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.min
+        }
+    }
+
+    /// Maximum latency in microseconds.
+    pub const fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Average latency in microseconds; 0 if the histogram is empty.
+    pub const fn avg(&self) -> u64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.sum / self.count
+        }
+    }
+
+    /// Sum of all latencies in microseconds.
+    pub const fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    /// Number of commands, including failed ones.
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Number of failed commands, including timeouts.
+    pub const fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Number of commands that failed with a timeout.
+    pub const fn timeouts(&self) -> u64 {
+        self.timeouts
+    }
+
+    /// Adds a command with the given latency and error to the histogram.
+    pub fn add(&mut self, latency: Duration, error: Option<&Error>) {
+        let micros = latency.as_micros().min(u128::from(u64::MAX)) as u64;
+        self.min = self.min.min(micros);
+        self.max = self.max.max(micros);
+        self.count += 1;
+        self.sum = self.sum.saturating_add(micros);
+
+        let mut upper = 1_000;
+        for (i, bucket) in self.buckets.iter_mut().enumerate() {
+            if (micros < upper) || (i == HIST_BUCKETS - 1) {
+                *bucket += 1;
+                break;
+            }
+            upper <<= 1;
+        }
+
+        if let Some(err) = error {
+            self.errors += 1;
+            if is_timeout(err) {
+                self.timeouts += 1;
+            }
+        }
+    }
+
+    /// Adds the counts of another histogram to this one.
+    pub fn merge(&mut self, other: &Histogram) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count += other.count;
+        self.sum = self.sum.saturating_add(other.sum);
+        for (s, o) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *s += *o;
+        }
+        self.errors += other.errors;
+        self.timeouts += other.timeouts;
+    }
+}
+
+// Histograms of a single node, one per command type, so that concurrent commands only contend
+// if they are of the same type and sent to the same node.
+#[derive(Debug, Default)]
+struct NodeHistograms([Mutex<Histogram>; COMMAND_TYPES.len()]);
+
+impl NodeHistograms {
+    fn get(&self, command_type: CommandType) -> Option<Histogram> {
+        let hist = *self.0[command_type as usize].lock();
+        if hist.count() == 0 {
+            None
+        } else {
+            Some(hist)
+        }
+    }
+}
+
+/// Built-in `MetricsListener` that keeps a latency histogram per command type and node.
+#[derive(Debug, Default)]
+pub struct LatencyMetrics {
+    nodes: RwLock<HashMap<Arc<str>, Arc<NodeHistograms>>>,
+}
+
+impl LatencyMetrics {
+    /// Create a new instance without any recorded commands.
+    pub fn new() -> Self {
+        LatencyMetrics::default()
+    }
+
+    /// Returns a copy of the histogram of the given command type and node.
+    pub fn histogram(&self, command_type: CommandType, node: &str) -> Option<Histogram> {
+        self.nodes.read().get(node)?.get(command_type)
+    }
+
+    /// Returns a copy of all histograms, ordered by command type and node name.
+    pub fn histograms(&self) -> Vec<(CommandType, String, Histogram)> {
+        let nodes = self.nodes.read();
+        let mut names: Vec<&Arc<str>> = nodes.keys().collect();
+        names.sort();
+        let mut histograms = vec![];
+        for command_type in &COMMAND_TYPES {
+            for name in &names {
+                if let Some(hist) = nodes[*name].get(*command_type) {
+                    histograms.push((*command_type, name.to_string(), hist));
+                }
+            }
+        }
+        histograms
+    }
+
+    /// Returns the histogram of the given command type, merged across all nodes.
+    pub fn total(&self, command_type: CommandType) -> Histogram {
+        let mut total = Histogram::new();
+        for node in self.nodes.read().values() {
+            if let Some(hist) = node.get(command_type) {
+                total.merge(&hist);
+            }
+        }
+        total
+    }
+
+    /// Removes all recorded commands.
+    pub fn reset(&self) {
+        self.nodes.write().clear();
+    }
+
+    // Returns the histograms of the node, adding them on the first command sent to the node.
+    fn node_histograms(&self, node: &str) -> Arc<NodeHistograms> {
+        if let Some(histograms) = self.nodes.read().get(node) {
+            return histograms.clone();
+        }
+        self.nodes
+            .write()
+            .entry(Arc::from(node))
+            .or_default()
+            .clone()
+    }
+}
+
+impl MetricsListener for LatencyMetrics {
+    fn command_completed(
+        &self,
+        command_type: CommandType,
+        node: &str,
+        latency: Duration,
+        error: Option<&Error>,
+    ) {
+        self.node_histograms(node).0[command_type as usize]
+            .lock()
+            .add(latency, error);
+    }
+}
+
+// Reports a completed command to the metrics listener of the client policy, if any.
+pub(crate) fn record(node: &Node, command_type: CommandType, start: Instant, result: &Result<()>) {
+    if let Some(ref listener) = node.client_policy().metrics_listener {
+        listener.command_completed(
+            command_type,
+            node.name(),
+            start.elapsed(),
+            result.as_ref().err(),
+        );
+    }
+}
+
+fn is_timeout(err: &Error) -> bool {
+    match *err.kind() {
+        ErrorKind::Timeout(_) | ErrorKind::ServerError(ResultCode::Timeout) => true,
+        _ => commands::is_socket_timeout(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_add() {
+        let mut hist = Histogram::new();
+        let err: Error = ErrorKind::Connection("reset".into()).into();
+        let timeout: Error = ErrorKind::Timeout("timeout".into()).into();
+        for i in 0..10 {
+            let error = match i % 3 {
+                1 => Some(&err),
+                2 => Some(&timeout),
+                _ => None,
+            };
+            hist.add(Duration::from_millis(i), error);
+        }
+        assert_eq!(hist.buckets(), &[1, 1, 2, 4, 2, 0]);
+        assert_eq!(hist.min(), 0);
+        assert_eq!(hist.max(), 9_000);
+        assert_eq!(hist.avg(), 4_500);
+        assert_eq!(hist.count(), 10);
+        assert_eq!(hist.errors(), 6);
+        assert_eq!(hist.timeouts(), 3);
+
+        hist.add(Duration::from_millis(42), None);
+        assert_eq!(hist.buckets(), &[1, 1, 2, 4, 2, 1]);
+        assert_eq!(Histogram::bucket_bound(4), Some(Duration::from_millis(16)));
+        assert_eq!(Histogram::bucket_bound(5), None);
+    }
+
+    #[test]
+    fn latency_metrics() {
+        let metrics = LatencyMetrics::new();
+        let err: Error = ErrorKind::ServerError(ResultCode::KeyNotFoundError).into();
+        metrics.command_completed(CommandType::Read, "B", Duration::from_millis(1), None);
+        metrics.command_completed(CommandType::Read, "A", Duration::from_millis(3), Some(&err));
+        metrics.command_completed(CommandType::Write, "A", Duration::from_millis(1), None);
+
+        let read = metrics.histogram(CommandType::Read, "A").unwrap();
+        assert_eq!(read.count(), 1);
+        assert_eq!(read.errors(), 1);
+        assert_eq!(read.timeouts(), 0);
+        assert_eq!(metrics.total(CommandType::Read).count(), 2);
+        assert!(metrics.histogram(CommandType::Query, "A").is_none());
+
+        let keys: Vec<_> = metrics
+            .histograms()
+            .into_iter()
+            .map(|(command_type, node, _)| (command_type, node))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (CommandType::Read, "A".to_owned()),
+                (CommandType::Read, "B".to_owned()),
+                (CommandType::Write, "A".to_owned()),
+            ]
+        );
+
+        metrics.reset();
+        assert!(metrics.histograms().is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt::Write;
+
+use super::{CommandType, Histogram, LatencyMetrics};
+
+impl LatencyMetrics {
+    /// Renders all histograms in the Prometheus text exposition format.
+    ///
+    /// Latencies are exported as the `aerospike_command_latency_seconds` histogram; errors and
+    /// timeouts as the `aerospike_command_errors_total` and `aerospike_command_timeouts_total`
+    /// counters. All metrics are labelled with the command type and node name.
+    pub fn to_prometheus(&self) -> String {
+        let histograms = self.histograms();
+        let mut out = String::new();
+
+        out.push_str(
+            "# HELP aerospike_command_latency_seconds Command latency including retries.\n",
+        );
+        out.push_str("# TYPE aerospike_command_latency_seconds histogram\n");
+        for (command_type, node, hist) in &histograms {
+            let labels = format!(
+                "command=\"{}\",node=\"{}\"",
+                command_type,
+                escape_label(node)
+            );
+            let mut cumulative = 0;
+            for (i, count) in hist.buckets().iter().enumerate() {
+                cumulative += count;
+                let le = match Histogram::bucket_bound(i) {
+                    Some(bound) => bound.as_secs_f64().to_string(),
+                    None => "+Inf".to_owned(),
+                };
+                let _ = writeln!(
+                    out,
+                    "aerospike_command_latency_seconds_bucket{{{},le=\"{}\"}} {}",
+                    labels, le, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "aerospike_command_latency_seconds_sum{{{}}} {}",
+                labels,
+                hist.sum() as f64 / 1_000_000.0
+            );
+            let _ = writeln!(
+                out,
+                "aerospike_command_latency_seconds_count{{{}}} {}",
+                labels,
+                hist.count()
+            );
+        }
+
+        write_counter(
+            &mut out,
+            &histograms,
+            "aerospike_command_errors_total",
+            "Number of failed commands.",
+            Histogram::errors,
+        );
+        write_counter(
+            &mut out,
+            &histograms,
+            "aerospike_command_timeouts_total",
+            "Number of commands that failed with a timeout.",
+            Histogram::timeouts,
+        );
+        out
+    }
+}
+
+fn write_counter<F: Fn(&Histogram) -> u64>(
+    out: &mut String,
+    histograms: &[(CommandType, String, Histogram)],
+    name: &str,
+    help: &str,
+    value: F,
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    for (command_type, node, hist) in histograms {
+        let _ = writeln!(
+            out,
+            "{}{{command=\"{}\",node=\"{}\"}} {}",
+            name,
+            command_type,
+            escape_label(node),
+            value(hist)
+        );
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::errors::{Error, ErrorKind};
+    use crate::metrics::{CommandType, LatencyMetrics, MetricsListener};
+
+    #[test]
+    fn to_prometheus() {
+        let metrics = LatencyMetrics::new();
+        let err: Error = ErrorKind::Timeout("timeout".into()).into();
+        metrics.command_completed(CommandType::Read, "A1", Duration::from_micros(500), None);
+        metrics.command_completed(
+            CommandType::Read,
+            "A1",
+            Duration::from_millis(3),
+            Some(&err),
+        );
+
+        let text = metrics.to_prometheus();
+        assert!(text.contains("# TYPE aerospike_command_latency_seconds histogram\n"));
+        assert!(text.contains(
+            "aerospike_command_latency_seconds_bucket{command=\"read\",node=\"A1\",le=\"0.001\"} 1\n"
+        ));
+        assert!(text.contains(
+            "aerospike_command_latency_seconds_bucket{command=\"read\",node=\"A1\",le=\"0.004\"} 2\n"
+        ));
+        assert!(text.contains(
+            "aerospike_command_latency_seconds_bucket{command=\"read\",node=\"A1\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains(
+            "aerospike_command_latency_seconds_sum{command=\"read\",node=\"A1\"} 0.0035\n"
+        ));
+        assert!(text
+            .contains("aerospike_command_latency_seconds_count{command=\"read\",node=\"A1\"} 2\n"));
+        assert!(text.contains("aerospike_command_errors_total{command=\"read\",node=\"A1\"} 1\n"));
+        assert!(text.contains("aerospike_command_timeouts_total{command=\"read\",node=\"A1\"} 1\n"));
+    }
+}
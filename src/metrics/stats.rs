@@ -0,0 +1,110 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::Node;
+
+/// Snapshot of the client-side state of a cluster node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeStats {
+    /// Name of the node.
+    pub name: String,
+    /// Address of the node.
+    pub address: String,
+    /// Number of open connections to the node, both idle and in use.
+    pub connections: usize,
+    /// Stats of the callers waiting for a connection to the node.
+    pub pool: PoolStats,
+    /// Number of transient command errors within the current error rate window.
+    pub error_count: usize,
+    /// Number of times the node exceeded `ClientPolicy::max_error_rate` within a window and
+    /// commands to the node were rejected.
+    pub error_rate_trips: usize,
+}
+
+impl NodeStats {
+    pub(crate) fn new(node: &Node) -> Self {
+        NodeStats {
+            name: node.name().to_owned(),
+            address: node.address().to_owned(),
+            connections: node.connection_count(),
+            pool: node.pool_stats(),
+            error_count: node.error_count(),
+            error_rate_trips: node.error_rate_trips(),
+        }
+    }
+}
+
+/// Stats of the callers waiting for a connection when the connection pool of a node is
+/// exhausted. See `ClientPolicy::conn_acquire_timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStats {
+    /// Number of callers currently waiting for a connection.
+    pub waiting: usize,
+    /// Maximum number of callers waiting for a connection at the same time.
+    pub max_waiting: usize,
+    /// Number of times a caller had to wait for a connection.
+    pub waits: u64,
+    /// Total time callers spent waiting for a connection.
+    pub wait_time: Duration,
+    /// Longest time a caller spent waiting for a connection.
+    pub max_wait_time: Duration,
+    /// Number of times a caller gave up waiting for a connection.
+    pub acquire_timeouts: u64,
+}
+
+impl PoolStats {
+    /// Average time a caller waited for a connection.
+    pub fn avg_wait_time(&self) -> Duration {
+        if self.waits == 0 {
+            return Duration::from_secs(0);
+        }
+        Duration::from_nanos((self.wait_time.as_nanos() / u128::from(self.waits)) as u64)
+    }
+
+    pub(crate) fn merge(&self, other: &PoolStats) -> PoolStats {
+        PoolStats {
+            waiting: self.waiting + other.waiting,
+            max_waiting: self.max_waiting.max(other.max_waiting),
+            waits: self.waits + other.waits,
+            wait_time: self.wait_time + other.wait_time,
+            max_wait_time: self.max_wait_time.max(other.max_wait_time),
+            acquire_timeouts: self.acquire_timeouts + other.acquire_timeouts,
+        }
+    }
+}
+
+/// Snapshot of the client-side state of all active cluster nodes, as returned by
+/// `Client::stats`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClientStats {
+    /// Stats of each active node.
+    pub nodes: Vec<NodeStats>,
+}
+
+impl ClientStats {
+    pub(crate) fn new(nodes: &[Arc<Node>]) -> Self {
+        ClientStats {
+            nodes: nodes.iter().map(|node| NodeStats::new(node)).collect(),
+        }
+    }
+
+    /// Total number of error rate trips of all nodes.
+    pub fn error_rate_trips(&self) -> usize {
+        self.nodes.iter().map(|node| node.error_rate_trips).sum()
+    }
+}
@@ -14,11 +14,13 @@
 // the License.
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::vec::Vec;
 
 use crate::commands::buffer::Buffer;
 use crate::commands::ParticleType;
 use crate::errors::{ErrorKind, Result};
+use crate::msgpack::encoder::{MSGPACK_CMP_INFINITY, MSGPACK_CMP_WILDCARD, MSGPACK_EXT_CMP};
 use crate::value::Value;
 
 pub fn unpack_value_list(buf: &mut Buffer) -> Result<Value> {
@@ -91,7 +93,7 @@ fn unpack_blob(buf: &mut Buffer, count: usize) -> Result<Value> {
     let vtype = buf.read_u8(None)?;
     let count = count - 1;
 
-    match ParticleType::from(vtype) {
+    match ParticleType::try_from(vtype)? {
         ParticleType::STRING => {
             let val = buf.read_str(count)?;
             Ok(Value::String(val))
@@ -162,6 +164,17 @@ fn unpack_value(buf: &mut Buffer) -> Result<Value> {
         0xd1 => Ok(Value::from(buf.read_i16(None)?)),
         0xd2 => Ok(Value::from(buf.read_i32(None)?)),
         0xd3 => Ok(Value::from(buf.read_i64(None)?)),
+        0xd4 if buf.peek() == MSGPACK_EXT_CMP => {
+            buf.skip_bytes(1);
+            match buf.read_u8(None)? {
+                MSGPACK_CMP_WILDCARD => Ok(Value::Wildcard),
+                MSGPACK_CMP_INFINITY => Ok(Value::Infinity),
+                value => {
+                    warn!("Skipping over unknown comparison extension value {}", value);
+                    Ok(Value::Nil)
+                }
+            }
+        }
         0xd4 => {
             warn!("Skipping over type extension with 1 byte");
             let count = (1 + 1) as usize;
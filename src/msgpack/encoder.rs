@@ -41,6 +41,8 @@ pub fn pack_value(buf: &mut Option<&mut Buffer>, val: &Value) -> Result<usize> {
         Value::HashMap(ref val) => pack_map(buf, val),
         Value::OrderedMap(_) => panic!("Ordered maps are not supported in this encoder."),
         Value::GeoJSON(ref val) => pack_geo_json(buf, val),
+        Value::Wildcard => pack_cmp_ext(buf, MSGPACK_CMP_WILDCARD),
+        Value::Infinity => pack_cmp_ext(buf, MSGPACK_CMP_INFINITY),
     }
 }
 
@@ -90,6 +92,7 @@ pub fn pack_cdt_op(
                 CdtArgument::Value(value) => pack_value(buf, value)?,
                 CdtArgument::List(list) => pack_array(buf, list)?,
                 CdtArgument::Map(map) => pack_map(buf, map)?,
+                CdtArgument::OrderedMap(map) => pack_ordered_map(buf, map)?,
                 CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val))?,
             }
         }
@@ -115,6 +118,7 @@ pub fn pack_hll_op(
                 CdtArgument::Value(value) => pack_value(buf, value)?,
                 CdtArgument::List(list) => pack_array(buf, list)?,
                 CdtArgument::Map(map) => pack_map(buf, map)?,
+                CdtArgument::OrderedMap(map) => pack_ordered_map(buf, map)?,
                 CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val))?,
             }
         }
@@ -155,6 +159,7 @@ pub fn pack_cdt_bit_op(
                 CdtArgument::Value(value) => pack_value(buf, value)?,
                 CdtArgument::List(list) => pack_array(buf, list)?,
                 CdtArgument::Map(map) => pack_map(buf, map)?,
+                CdtArgument::OrderedMap(map) => pack_ordered_map(buf, map)?,
                 CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val))?,
             }
         }
@@ -187,6 +192,26 @@ pub fn pack_map(buf: &mut Option<&mut Buffer>, map: &HashMap<Value, Value>) -> R
     Ok(size)
 }
 
+// Writes the map entries in key order, as expected by the server for key-ordered maps.
+#[doc(hidden)]
+pub fn pack_ordered_map(
+    buf: &mut Option<&mut Buffer>,
+    map: &HashMap<Value, Value>,
+) -> Result<usize> {
+    let mut size = 0;
+
+    let mut entries: Vec<(&Value, &Value)> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    size += pack_map_begin(buf, map.len())?;
+    for (key, val) in entries {
+        size += pack_value(buf, key)?;
+        size += pack_value(buf, val)?;
+    }
+
+    Ok(size)
+}
+
 /// ///////////////////////////////////////////////////////////////////
 
 const MSGPACK_MARKER_NIL: u8 = 0xc0;
@@ -230,6 +255,20 @@ pub fn pack_byte(buf: &mut Option<&mut Buffer>, marker: u8, value: u8) -> Result
     Ok(2)
 }
 
+// Extension type and values used by the server for the special CDT comparison values.
+pub const MSGPACK_EXT_CMP: u8 = 0xff;
+pub const MSGPACK_CMP_WILDCARD: u8 = 0x00;
+pub const MSGPACK_CMP_INFINITY: u8 = 0x01;
+
+fn pack_cmp_ext(buf: &mut Option<&mut Buffer>, value: u8) -> Result<usize> {
+    if let Some(ref mut buf) = *buf {
+        buf.write_u8(0xd4)?;
+        buf.write_u8(MSGPACK_EXT_CMP)?;
+        buf.write_u8(value)?;
+    }
+    Ok(3)
+}
+
 #[doc(hidden)]
 pub fn pack_nil(buf: &mut Option<&mut Buffer>) -> Result<usize> {
     if let Some(ref mut buf) = *buf {
@@ -13,15 +13,15 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use std::io::prelude::*;
-use std::net::{Shutdown, TcpStream, ToSocketAddrs};
 use std::ops::Add;
 use std::time::{Duration, Instant};
 
 use crate::commands::admin_command::AdminCommand;
 use crate::commands::buffer::Buffer;
 use crate::errors::Result;
+use crate::net::Host;
 use crate::policy::ClientPolicy;
+use crate::transport::Stream;
 
 #[derive(Debug)]
 pub struct Connection {
@@ -32,7 +32,7 @@ pub struct Connection {
     idle_deadline: Option<Instant>,
 
     // connection object
-    conn: TcpStream,
+    conn: Box<dyn Stream>,
 
     bytes_read: usize,
 
@@ -40,8 +40,18 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new<T: ToSocketAddrs>(addr: T, policy: &ClientPolicy) -> Result<Self> {
-        let stream = TcpStream::connect(addr)?;
+    pub fn new(host: &Host, policy: &ClientPolicy) -> Result<Self> {
+        Connection::with_timeout(host, policy, policy.timeout)
+    }
+
+    // Opens a connection, bounding the time spent connecting and authenticating by `timeout`
+    // instead of the policy's connection timeout.
+    pub fn with_timeout(
+        host: &Host,
+        policy: &ClientPolicy,
+        timeout: Option<Duration>,
+    ) -> Result<Self> {
+        let stream = policy.transport.connect(host, timeout)?;
         let mut conn = Connection {
             buffer: Buffer::new(policy.buffer_reclaim_threshold),
             bytes_read: 0,
@@ -53,13 +63,14 @@ impl Connection {
                 Some(timeout) => Some(Instant::now() + timeout),
             },
         };
+        conn.set_timeout(timeout)?;
         conn.authenticate(&policy.user_password)?;
         conn.refresh();
         Ok(conn)
     }
 
     pub fn close(&mut self) {
-        let _ = self.conn.shutdown(Shutdown::Both);
+        let _ = self.conn.shutdown();
     }
 
     pub fn flush(&mut self) -> Result<()> {
@@ -77,6 +88,18 @@ impl Connection {
         Ok(())
     }
 
+    // Reads the given number of bytes from the socket and appends them to the buffer, keeping
+    // the data read previously.
+    pub fn append_buffer(&mut self, size: usize) -> Result<()> {
+        let offset = self.buffer.data_buffer.len();
+        self.buffer.resize_buffer(offset + size)?;
+        self.conn
+            .read_exact(&mut self.buffer.data_buffer[offset..])?;
+        self.bytes_read += size;
+        self.refresh();
+        Ok(())
+    }
+
     pub fn write(&mut self, buf: &[u8]) -> Result<()> {
         self.conn.write_all(buf)?;
         self.refresh();
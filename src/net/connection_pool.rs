@@ -17,11 +17,12 @@ use std::collections::VecDeque;
 use std::ops::{Deref, DerefMut, Drop};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use parking_lot::Mutex;
+use parking_lot::{Condvar, Mutex, MutexGuard};
 
 use crate::errors::{Error, ErrorKind, Result};
+use crate::metrics::PoolStats;
 use crate::net::{Connection, Host};
 use crate::policy::ClientPolicy;
 
@@ -32,12 +33,20 @@ struct IdleConnection(Connection);
 struct QueueInternals {
     connections: VecDeque<IdleConnection>,
     num_conns: usize,
+    // tickets of the callers waiting for a connection, in arrival order
+    waiters: VecDeque<u64>,
+    next_ticket: u64,
+    stats: PoolStats,
 }
 
 #[derive(Debug)]
 struct SharedQueue {
     internals: Mutex<QueueInternals>,
+    // signaled when a connection is returned to the queue or a waiter leaves it
+    available: Condvar,
     capacity: usize,
+    min_size: usize,
+    max_waiters: usize,
     host: Host,
     policy: ClientPolicy,
 }
@@ -46,46 +55,74 @@ struct SharedQueue {
 struct Queue(Arc<SharedQueue>);
 
 impl Queue {
-    pub fn with_capacity(capacity: usize, host: Host, policy: ClientPolicy) -> Self {
+    pub fn with_capacity(
+        capacity: usize,
+        min_size: usize,
+        max_waiters: usize,
+        host: Host,
+        policy: ClientPolicy,
+    ) -> Self {
         let internals = QueueInternals {
             connections: VecDeque::with_capacity(capacity),
             num_conns: 0,
+            waiters: VecDeque::new(),
+            next_ticket: 0,
+            stats: PoolStats::default(),
         };
         let shared = SharedQueue {
             internals: Mutex::new(internals),
+            available: Condvar::new(),
             capacity,
+            min_size,
+            max_waiters,
             host,
             policy,
         };
         Queue(Arc::new(shared))
     }
 
-    pub fn get(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
-        let mut internals = self.0.internals.lock();
-        let connection;
-        loop {
-            if let Some(IdleConnection(mut conn)) = internals.connections.pop_front() {
-                if conn.is_idle() {
-                    internals.num_conns -= 1;
-                    conn.close();
-                    continue;
-                }
-                connection = conn;
-                break;
-            } else {
-                if internals.num_conns >= self.0.capacity {
-                    bail!(ErrorKind::NoMoreConnections);
+    // Takes a connection from the queue, opening a new one if the queue is below capacity. If the
+    // queue is exhausted and `wait` is set, the caller waits up to `wait` for a connection to be
+    // returned. Waiting callers are served in the order they arrived. New connections are opened
+    // without holding the lock.
+    pub fn get(
+        &self,
+        timeout: Option<Duration>,
+        wait: Option<Duration>,
+    ) -> Result<PooledConnection> {
+        let acquired = {
+            let mut internals = self.0.internals.lock();
+            let mut ticket = None;
+            let result = self.acquire(&mut internals, wait, &mut ticket);
+            if let Some((ticket, wait_start)) = ticket {
+                internals.waiters.retain(|waiter| *waiter != ticket);
+                let wait_time = wait_start.elapsed();
+                internals.stats.waits += 1;
+                internals.stats.wait_time += wait_time;
+                internals.stats.max_wait_time = internals.stats.max_wait_time.max(wait_time);
+                if let Err(Error(ErrorKind::NoMoreConnections, _)) = result {
+                    internals.stats.acquire_timeouts += 1;
                 }
-                let conn = Connection::new(&self.0.host, &self.0.policy)?;
-                internals.num_conns += 1;
-                connection = conn;
-                break;
+                // the next waiter might be able to take a connection now
+                self.0.available.notify_all();
             }
+            result?
+        };
+
+        let connection = match acquired {
+            Some(conn) => conn,
+            None => match Connection::new(&self.0.host, &self.0.policy) {
+                Ok(conn) => conn,
+                Err(err) => {
+                    self.release();
+                    return Err(err);
+                }
+            },
+        };
+        if let Err(err) = connection.set_timeout(timeout) {
+            self.drop_conn(connection);
+            return Err(err);
         }
-        connection.set_timeout(timeout).map_err(|err| {
-            internals.num_conns -= 1;
-            err
-        })?;
 
         Ok(PooledConnection {
             queue: self.clone(),
@@ -93,22 +130,166 @@ impl Queue {
         })
     }
 
+    // Returns an idle connection, or `None` if a slot for a new connection has been reserved.
+    fn acquire(
+        &self,
+        internals: &mut MutexGuard<QueueInternals>,
+        wait: Option<Duration>,
+        ticket: &mut Option<(u64, Instant)>,
+    ) -> Result<Option<Connection>> {
+        let wait = wait.filter(|wait| *wait > Duration::from_secs(0));
+        loop {
+            // Callers must not overtake the ones waiting ahead of them: a caller is only served
+            // if there are enough idle connections or free slots left for all of those.
+            let ahead = match *ticket {
+                Some((ticket, _)) => internals
+                    .waiters
+                    .iter()
+                    .position(|waiter| *waiter == ticket)
+                    .unwrap_or(0),
+                None => internals.waiters.len(),
+            };
+            let available =
+                internals.connections.len() + self.0.capacity.saturating_sub(internals.num_conns);
+            if available > ahead {
+                if let Some(IdleConnection(mut conn)) = internals.connections.pop_front() {
+                    // Connections up to the minimum size are kept regardless of their idle time.
+                    if !conn.is_idle() || internals.num_conns <= self.0.min_size {
+                        return Ok(Some(conn));
+                    }
+                    conn.close();
+                    internals.num_conns -= 1;
+                    continue;
+                }
+                if internals.num_conns < self.0.capacity {
+                    internals.num_conns += 1;
+                    return Ok(None);
+                }
+            }
+
+            // The queue is exhausted; wait for a connection to be returned.
+            let wait = match wait {
+                Some(wait) => wait,
+                None => bail!(ErrorKind::NoMoreConnections),
+            };
+            let wait_start = match *ticket {
+                Some((_, wait_start)) => wait_start,
+                None if internals.waiters.len() < self.0.max_waiters => {
+                    let wait_ticket = internals.next_ticket;
+                    internals.next_ticket += 1;
+                    internals.waiters.push_back(wait_ticket);
+                    let depth = internals.waiters.len();
+                    internals.stats.max_waiting = internals.stats.max_waiting.max(depth);
+                    let wait_start = Instant::now();
+                    *ticket = Some((wait_ticket, wait_start));
+                    wait_start
+                }
+                None => bail!(ErrorKind::NoMoreConnections),
+            };
+            let deadline = wait_start + wait;
+            if Instant::now() >= deadline {
+                bail!(ErrorKind::NoMoreConnections);
+            }
+            self.0.available.wait_until(internals, deadline);
+        }
+    }
+
     pub fn put_back(&self, mut conn: Connection) {
         let mut internals = self.0.internals.lock();
-        if internals.num_conns < self.0.capacity {
+        if internals.connections.len() < self.0.capacity {
             internals.connections.push_back(IdleConnection(conn));
         } else {
             conn.close();
             internals.num_conns -= 1;
         }
+        self.0.available.notify_all();
     }
 
     pub fn drop_conn(&self, mut conn: Connection) {
+        self.release();
+        conn.close();
+    }
+
+    // Frees the slot of a connection that has been closed or could not be opened.
+    fn release(&self) {
         {
             let mut internals = self.0.internals.lock();
             internals.num_conns -= 1;
         }
-        conn.close();
+        self.0.available.notify_all();
+    }
+
+    // Opens new connections until the queue holds at least `count` connections, or its capacity
+    // is reached. The connections are opened without holding the lock. If a deadline is given,
+    // stops early once it has passed and bounds each connection attempt by the time left until
+    // then. Returns the number of connections opened.
+    pub fn fill(&self, count: usize, deadline: Option<Instant>) -> Result<usize> {
+        let count = count.min(self.0.capacity);
+        let mut opened = 0;
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining == Duration::from_secs(0) {
+                        return Ok(opened);
+                    }
+                    let timeout = self.0.policy.timeout;
+                    Some(timeout.map_or(remaining, |timeout| timeout.min(remaining)))
+                }
+                None => self.0.policy.timeout,
+            };
+            {
+                let mut internals = self.0.internals.lock();
+                if internals.num_conns >= count {
+                    return Ok(opened);
+                }
+                internals.num_conns += 1;
+            }
+            match Connection::with_timeout(&self.0.host, &self.0.policy, timeout) {
+                Ok(conn) => {
+                    let mut internals = self.0.internals.lock();
+                    internals.connections.push_back(IdleConnection(conn));
+                    self.0.available.notify_all();
+                    opened += 1;
+                }
+                Err(err) => {
+                    let mut internals = self.0.internals.lock();
+                    internals.num_conns -= 1;
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    // Closes idle connections, keeping at least `min_size` connections in the queue. Connections
+    // are returned to the back of the queue, so the least recently used ones are at the front.
+    pub fn trim_idle(&self) -> usize {
+        let mut internals = self.0.internals.lock();
+        let mut closed = 0;
+        while internals.num_conns > self.0.min_size {
+            match internals.connections.front() {
+                Some(IdleConnection(conn)) if conn.is_idle() => (),
+                _ => break,
+            }
+            if let Some(IdleConnection(mut conn)) = internals.connections.pop_front() {
+                conn.close();
+                internals.num_conns -= 1;
+                closed += 1;
+            }
+        }
+        closed
+    }
+
+    pub fn num_conns(&self) -> usize {
+        self.0.internals.lock().num_conns
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        let internals = self.0.internals.lock();
+        PoolStats {
+            waiting: internals.waiters.len(),
+            ..internals.stats
+        }
     }
 
     pub fn clear(&mut self) {
@@ -129,6 +310,10 @@ impl Clone for Queue {
 #[derive(Debug)]
 pub struct ConnectionPool {
     num_queues: usize,
+    min_conns: usize,
+    // time the tend thread may spend opening connections to the node in each tend cycle
+    connect_budget: Duration,
+    acquire_timeout: Option<Duration>,
     queues: Vec<Queue>,
     queue_counter: AtomicUsize,
 }
@@ -136,10 +321,17 @@ pub struct ConnectionPool {
 impl ConnectionPool {
     pub fn new(host: Host, policy: ClientPolicy) -> Self {
         let num_conns = policy.max_conns_per_node;
+        let min_conns = policy.min_conns_per_node.min(num_conns);
         let num_queues = policy.conn_pools_per_node;
-        let queues = ConnectionPool::initialize_queues(num_conns, num_queues, host, policy);
+        let acquire_timeout = policy.conn_acquire_timeout;
+        let connect_budget = policy.tend_interval;
+        let queues =
+            ConnectionPool::initialize_queues(num_conns, min_conns, num_queues, host, policy);
         ConnectionPool {
             num_queues,
+            min_conns,
+            connect_budget,
+            acquire_timeout,
             queues,
             queue_counter: AtomicUsize::default(),
         }
@@ -147,41 +339,108 @@ impl ConnectionPool {
 
     fn initialize_queues(
         num_conns: usize,
+        min_conns: usize,
         num_queues: usize,
         host: Host,
         policy: ClientPolicy,
     ) -> Vec<Queue> {
-        let max = num_conns / num_queues;
-        let mut rem = num_conns % num_queues;
-        let mut queues = Vec::with_capacity(num_queues);
-        for _ in 0..num_queues {
-            let mut capacity = max;
-            if rem > 0 {
-                capacity += 1;
-                rem -= 1;
-            }
-            queues.push(Queue::with_capacity(capacity, host.clone(), policy.clone()));
-        }
-        queues
+        let capacities = ConnectionPool::split(num_conns, num_queues);
+        let min_sizes = ConnectionPool::split(min_conns, num_queues);
+        let max_waiters = ConnectionPool::split(policy.max_conn_waiters, num_queues);
+        capacities
+            .into_iter()
+            .zip(min_sizes)
+            .zip(max_waiters)
+            .map(|((capacity, min_size), max_waiters)| {
+                Queue::with_capacity(
+                    capacity,
+                    min_size,
+                    max_waiters,
+                    host.clone(),
+                    policy.clone(),
+                )
+            })
+            .collect()
+    }
+
+    // Splits `count` connections as evenly as possible across `num_queues` queues.
+    fn split(count: usize, num_queues: usize) -> Vec<usize> {
+        let max = count / num_queues;
+        let rem = count % num_queues;
+        (0..num_queues)
+            .map(|i| if i < rem { max + 1 } else { max })
+            .collect()
     }
 
     pub fn get(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
+        // the wait for a connection is bounded by the socket timeout of the command
+        let wait = self
+            .acquire_timeout
+            .map(|wait| timeout.map_or(wait, |timeout| wait.min(timeout)));
         if self.num_queues == 1 {
-            self.queues[0].get(timeout)
-        } else {
-            let mut attempts = self.num_queues;
-            loop {
-                let i = self.queue_counter.fetch_add(1, Ordering::Relaxed);
-                let connection = self.queues[i % self.num_queues].get(timeout);
-                if let Err(Error(ErrorKind::NoMoreConnections, _)) = connection {
-                    attempts -= 1;
-                    if attempts > 0 {
-                        continue;
-                    }
-                }
+            return self.queues[0].get(timeout, wait);
+        }
+
+        // Try all queues before waiting on one of them.
+        let i = self.queue_counter.fetch_add(1, Ordering::Relaxed);
+        for attempt in 0..self.num_queues {
+            let connection = self.queues[(i + attempt) % self.num_queues].get(timeout, None);
+            if !matches!(connection, Err(Error(ErrorKind::NoMoreConnections, _))) {
                 return connection;
             }
         }
+        if wait.is_none() {
+            bail!(ErrorKind::NoMoreConnections);
+        }
+        self.queues[i % self.num_queues].get(timeout, wait)
+    }
+
+    // Opens connections until the pool holds at least `count` connections, capped by
+    // `max_conns_per_node`. Returns the number of connections opened.
+    pub fn warm_up(&self, count: usize) -> Result<usize> {
+        self.fill(count, None)
+    }
+
+    fn fill(&self, count: usize, deadline: Option<Instant>) -> Result<usize> {
+        let mut opened = 0;
+        for (queue, count) in self
+            .queues
+            .iter()
+            .zip(ConnectionPool::split(count, self.num_queues))
+        {
+            opened += queue.fill(count, deadline)?;
+        }
+        Ok(opened)
+    }
+
+    // Closes idle connections above `min_conns_per_node` and opens new connections to make up
+    // for connections closed below the minimum. Runs on the tend thread, so it spends at most one
+    // tend interval opening connections; the remaining ones are opened in the next tend cycle.
+    pub fn balance(&self) -> Result<()> {
+        for queue in &self.queues {
+            let closed = queue.trim_idle();
+            if closed > 0 {
+                debug!("Closed {} idle connections to {}", closed, queue.0.host);
+            }
+        }
+        if self.min_conns > 0 {
+            let deadline = Instant::now() + self.connect_budget;
+            self.fill(self.min_conns, Some(deadline))?;
+        }
+        Ok(())
+    }
+
+    // Number of open connections, both idle and in use.
+    pub fn num_conns(&self) -> usize {
+        self.queues.iter().map(Queue::num_conns).sum()
+    }
+
+    // Wait queue stats, summed over all queues of the pool.
+    pub fn stats(&self) -> PoolStats {
+        self.queues
+            .iter()
+            .map(Queue::stats)
+            .fold(PoolStats::default(), |total, stats| total.merge(&stats))
     }
 
     pub fn close(&mut self) {
@@ -225,3 +484,239 @@ impl DerefMut for PooledConnection {
         self.conn.as_mut().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use parking_lot::Mutex;
+
+    use super::{ConnectionPool, Queue};
+    use crate::errors::{Error, ErrorKind};
+    use crate::net::Host;
+    use crate::policy::ClientPolicy;
+    use crate::transport::{Stream, Transport};
+
+    #[derive(Debug)]
+    struct NullStream;
+
+    impl Stream for NullStream {
+        fn read_exact(&mut self, _buf: &mut [u8]) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn write_all(&mut self, _buf: &[u8]) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn set_write_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn shutdown(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct NullTransport {
+        connects: AtomicUsize,
+        // connect timeout of the last connection attempt
+        timeout: Mutex<Option<Duration>>,
+    }
+
+    impl Transport for NullTransport {
+        fn connect(&self, _host: &Host, timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+            self.connects.fetch_add(1, Ordering::SeqCst);
+            *self.timeout.lock() = timeout;
+            Ok(Box::new(NullStream))
+        }
+    }
+
+    fn queue(
+        capacity: usize,
+        min_size: usize,
+        max_waiters: usize,
+        idle_timeout: Option<Duration>,
+    ) -> (Queue, Arc<NullTransport>) {
+        let transport = Arc::new(NullTransport::default());
+        let policy = ClientPolicy {
+            idle_timeout,
+            transport: transport.clone(),
+            ..ClientPolicy::default()
+        };
+        let host = Host::new("localhost", 3000);
+        let queue = Queue::with_capacity(capacity, min_size, max_waiters, host, policy);
+        (queue, transport)
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(ConnectionPool::split(10, 1), vec![10]);
+        assert_eq!(ConnectionPool::split(10, 3), vec![4, 3, 3]);
+        assert_eq!(ConnectionPool::split(2, 4), vec![1, 1, 0, 0]);
+        assert_eq!(ConnectionPool::split(0, 2), vec![0, 0]);
+    }
+
+    #[test]
+    fn wait_for_connection() {
+        let host = Host::new("localhost", 3000);
+        let queue = Queue::with_capacity(0, 0, 1, host, ClientPolicy::default());
+
+        // without a wait, an exhausted queue fails right away
+        let res = queue.get(None, None);
+        assert!(matches!(res, Err(Error(ErrorKind::NoMoreConnections, _))));
+        assert_eq!(queue.stats().waits, 0);
+
+        let wait = Duration::from_millis(20);
+        let res = queue.get(None, Some(wait));
+        assert!(matches!(res, Err(Error(ErrorKind::NoMoreConnections, _))));
+        let stats = queue.stats();
+        assert_eq!(stats.waiting, 0);
+        assert_eq!(stats.max_waiting, 1);
+        assert_eq!(stats.waits, 1);
+        assert_eq!(stats.acquire_timeouts, 1);
+        assert!(stats.max_wait_time >= wait);
+        assert_eq!(stats.avg_wait_time(), stats.wait_time);
+    }
+
+    #[test]
+    fn keep_idle_connections_at_min_size() {
+        let idle_timeout = Duration::from_millis(5);
+        let (queue, transport) = queue(2, 1, 0, Some(idle_timeout));
+        assert_eq!(queue.fill(2, None).unwrap(), 2);
+        thread::sleep(idle_timeout * 2);
+
+        // the idle connection above the minimum size is closed, the one at the minimum size is
+        // handed out without opening a new connection
+        let conn = queue.get(None, None).unwrap();
+        assert!(conn.is_idle());
+        assert_eq!(queue.num_conns(), 1);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 2);
+        drop(conn);
+        assert_eq!(queue.trim_idle(), 0);
+    }
+
+    #[test]
+    fn fill_until_deadline() {
+        let (bounded, transport) = queue(2, 0, 0, None);
+
+        // no connections are opened once the deadline has passed
+        assert_eq!(bounded.fill(2, Some(Instant::now())).unwrap(), 0);
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 0);
+
+        // connection attempts are bounded by the time left until the deadline
+        let budget = Duration::from_secs(1);
+        let deadline = Instant::now() + budget;
+        assert_eq!(bounded.fill(2, Some(deadline)).unwrap(), 2);
+        assert!(transport.timeout.lock().unwrap() <= budget);
+
+        // without a deadline, the connect timeout of the client policy applies
+        let (unbounded, transport) = queue(2, 0, 0, None);
+        assert_eq!(unbounded.fill(1, None).unwrap(), 1);
+        assert_eq!(*transport.timeout.lock(), ClientPolicy::default().timeout);
+    }
+
+    // Waits until the given number of callers are waiting for a connection of the queue.
+    fn wait_for_waiters(queue: &Queue, count: usize) {
+        while queue.stats().waiting < count {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn fifo_hand_off() {
+        let (queue, transport) = queue(1, 0, 2, None);
+        let conn = queue.get(None, None).unwrap();
+
+        let served = Arc::new(Mutex::new(vec![]));
+        let waiters: Vec<_> = (0..2)
+            .map(|i| {
+                let handle = {
+                    let queue = queue.clone();
+                    let served = served.clone();
+                    thread::spawn(move || {
+                        let conn = queue.get(None, Some(Duration::from_secs(5))).unwrap();
+                        served.lock().push(i);
+                        thread::sleep(Duration::from_millis(10));
+                        drop(conn);
+                    })
+                };
+                wait_for_waiters(&queue, i + 1);
+                handle
+            })
+            .collect();
+
+        // a new caller does not overtake the waiting ones
+        let res = queue.get(None, None);
+        assert!(matches!(res, Err(Error(ErrorKind::NoMoreConnections, _))));
+
+        drop(conn);
+        for handle in waiters {
+            handle.join().unwrap();
+        }
+        assert_eq!(*served.lock(), vec![0, 1]);
+
+        // the connection is handed from one caller to the next
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 1);
+        assert_eq!(queue.num_conns(), 1);
+        assert_eq!(queue.stats().waits, 2);
+    }
+
+    #[test]
+    fn max_conn_waiters() {
+        let (queue, _) = queue(1, 0, 1, None);
+        let conn = queue.get(None, None).unwrap();
+
+        let waiter = {
+            let queue = queue.clone();
+            thread::spawn(move || queue.get(None, Some(Duration::from_secs(5))).is_ok())
+        };
+        wait_for_waiters(&queue, 1);
+
+        // the wait queue is full, so the caller is rejected without waiting
+        let start = Instant::now();
+        let res = queue.get(None, Some(Duration::from_secs(5)));
+        assert!(matches!(res, Err(Error(ErrorKind::NoMoreConnections, _))));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        drop(conn);
+        assert!(waiter.join().unwrap());
+        assert_eq!(queue.stats().max_waiting, 1);
+    }
+
+    #[test]
+    fn get_from_pool_with_waiters() {
+        let transport = Arc::new(NullTransport::default());
+        let policy = ClientPolicy {
+            max_conns_per_node: 4,
+            conn_pools_per_node: 2,
+            conn_acquire_timeout: None,
+            transport: transport.clone(),
+            ..ClientPolicy::default()
+        };
+        let pool = ConnectionPool::new(Host::new("localhost", 3000), policy);
+        assert_eq!(pool.warm_up(4).unwrap(), 4);
+
+        // a caller that is about to take one of the two idle connections of each queue
+        for queue in &pool.queues {
+            queue.0.internals.lock().waiters.push_back(u64::MAX);
+        }
+
+        // new callers take the idle connections not claimed by the waiting callers
+        let first = pool.get(None).unwrap();
+        let second = pool.get(None).unwrap();
+        let res = pool.get(None);
+        assert!(matches!(res, Err(Error(ErrorKind::NoMoreConnections, _))));
+        drop((first, second));
+        assert_eq!(transport.connects.load(Ordering::SeqCst), 4);
+    }
+}
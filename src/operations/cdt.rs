@@ -29,6 +29,8 @@ pub enum CdtArgument<'a> {
     Value(&'a Value),
     List(&'a [Value]),
     Map(&'a HashMap<Value, Value>),
+    // Map written in key order.
+    OrderedMap(&'a HashMap<Value, Value>),
 }
 
 pub type OperationEncoder =
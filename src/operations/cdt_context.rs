@@ -13,6 +13,10 @@
 // limitations under the License.
 
 //! Operation Context for nested Operations
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::errors::{ErrorKind, Result};
 use crate::operations::lists::{list_order_flag, ListOrderType};
 use crate::operations::MapOrder;
 use crate::Value;
@@ -36,7 +40,7 @@ pub enum CtxType {
 /// for the current level.
 /// An array of CTX identifies location of the list/map on multiple
 /// levels on nesting.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CdtContext {
     /// Context Type
     pub id: u8,
@@ -147,3 +151,166 @@ pub const fn ctx_map_value(key: Value) -> CdtContext {
         value: key,
     }
 }
+
+/// Parses a document path into the list of contexts identifying the nested element. Path
+/// segments are separated by dots and select map keys; list indexes are given in brackets. Map
+/// keys containing dots or brackets can be quoted inside brackets. A leading `$` denoting the
+/// root of the document is optional.
+///
+/// Examples:
+/// `profile.addresses[0].city`: key "city" of the first item of the "addresses" list inside the
+/// "profile" map.
+/// `$.matrix[1][-1]`: last item of the second list inside the "matrix" list.
+/// `tags['a.b']`: key "a.b" of the "tags" map.
+pub fn ctx_from_path(path: &str) -> Result<Vec<CdtContext>> {
+    PathParser::new(path).read_path()
+}
+
+struct PathParser<'a> {
+    path: &'a str,
+    s: Peekable<Chars<'a>>,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(path: &'a str) -> Self {
+        PathParser {
+            path,
+            s: path.chars().peekable(),
+        }
+    }
+
+    fn read_path(&mut self) -> Result<Vec<CdtContext>> {
+        let mut ctx = Vec::new();
+        if self.s.peek() == Some(&'$') {
+            self.s.next();
+            match self.s.next() {
+                None => return Ok(ctx),
+                Some('.') => (),
+                Some('[') => ctx.push(self.read_bracket()?),
+                Some(_) => return Err(self.error("expected '.' or '[' after '$'")),
+            }
+        }
+        if self.s.peek().is_none() {
+            return Ok(ctx);
+        }
+
+        loop {
+            match self.s.peek() {
+                Some('[') => (),
+                _ => ctx.push(ctx_map_key(Value::from(self.read_key()?))),
+            }
+            while self.s.peek() == Some(&'[') {
+                self.s.next();
+                ctx.push(self.read_bracket()?);
+            }
+            match self.s.next() {
+                None => return Ok(ctx),
+                Some('.') => (),
+                Some(c) => return Err(self.error(&format!("unexpected character '{}'", c))),
+            }
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String> {
+        let mut key = String::new();
+        while let Some(&c) = self.s.peek() {
+            if c == '.' || c == '[' || c == ']' {
+                break;
+            }
+            key.push(c);
+            self.s.next();
+        }
+        if key.is_empty() {
+            return Err(self.error("empty map key"));
+        }
+        Ok(key)
+    }
+
+    // Reads the remainder of a bracket expression; the opening bracket has been consumed.
+    fn read_bracket(&mut self) -> Result<CdtContext> {
+        let ctx = match self.s.peek() {
+            Some(&q) if q == '\'' || q == '"' => {
+                self.s.next();
+                let mut key = String::new();
+                loop {
+                    match self.s.next() {
+                        Some(c) if c == q => break,
+                        Some(c) => key.push(c),
+                        None => return Err(self.error("unterminated quoted key")),
+                    }
+                }
+                ctx_map_key(Value::from(key))
+            }
+            _ => {
+                let mut index = String::new();
+                while let Some(&c) = self.s.peek() {
+                    if c == ']' {
+                        break;
+                    }
+                    index.push(c);
+                    self.s.next();
+                }
+                match index.trim().parse() {
+                    Ok(index) => ctx_list_index(index),
+                    Err(_) => {
+                        return Err(self.error(&format!("invalid list index '{}'", index)));
+                    }
+                }
+            }
+        };
+        match self.s.next() {
+            Some(']') => Ok(ctx),
+            _ => Err(self.error("expected ']'")),
+        }
+    }
+
+    fn error(&self, reason: &str) -> crate::errors::Error {
+        ErrorKind::InvalidArgument(format!("Invalid document path '{}': {}", self.path, reason))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ctx_from_path() {
+        assert_eq!(
+            super::ctx_from_path("profile.addresses[0].city").unwrap(),
+            vec![
+                ctx_map_key(Value::from("profile")),
+                ctx_map_key(Value::from("addresses")),
+                ctx_list_index(0),
+                ctx_map_key(Value::from("city")),
+            ]
+        );
+        assert_eq!(
+            super::ctx_from_path("$.matrix[1][-1]").unwrap(),
+            vec![
+                ctx_map_key(Value::from("matrix")),
+                ctx_list_index(1),
+                ctx_list_index(-1),
+            ]
+        );
+        assert_eq!(
+            super::ctx_from_path("$[2]['a.b'][\"c]\"]").unwrap(),
+            vec![
+                ctx_list_index(2),
+                ctx_map_key(Value::from("a.b")),
+                ctx_map_key(Value::from("c]")),
+            ]
+        );
+        assert!(super::ctx_from_path("").unwrap().is_empty());
+        assert!(super::ctx_from_path("$").unwrap().is_empty());
+    }
+
+    #[test]
+    fn ctx_from_invalid_path() {
+        for path in &[
+            "a..b", "a.", ".a", "a[x]", "a[1", "a['b]", "a]", "$a", "a[1]b",
+        ] {
+            assert!(super::ctx_from_path(path).is_err(), "{}", path);
+        }
+    }
+}
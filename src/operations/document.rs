@@ -0,0 +1,255 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Document operations on nested CDT map and list bins, addressed by path. Paths are parsed using
+//! `cdt_context::ctx_from_path`; each document operation compiles to a single map or list
+//! operation with the matching context, so that all operations can be sent in one
+//! `Client::operate` call.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use aerospike::*;
+//! use aerospike::operations::document::DocumentOps;
+//!
+//! # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+//! # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+//! # let key = as_key!("test", "test", "document");
+//! # let doc = as_map!("profile" => as_map!("addresses" => as_list!(as_map!("city" => "Munich"))),
+//! #     "visits" => 0);
+//! # client.put(&WritePolicy::default(), &key, &[as_bin!("doc", doc)]).unwrap();
+//! let mut doc = DocumentOps::new("doc");
+//! doc.put("profile.addresses[0].city", "Berlin")
+//!     .unwrap()
+//!     .increment("visits", 1)
+//!     .unwrap()
+//!     .get("profile.addresses[0]")
+//!     .unwrap();
+//! let record = client
+//!     .operate(&WritePolicy::default(), &key, &doc.operations())
+//!     .unwrap();
+//! println!("{:?}", record.bins.get("doc"));
+//! ```
+
+use crate::errors::{ErrorKind, Result};
+use crate::operations::cdt_context::{ctx_from_path, CdtContext, CtxType};
+use crate::operations::lists::{self, ListPolicy};
+use crate::operations::maps::{self, MapPolicy, MapReturnType};
+use crate::operations::Operation;
+use crate::Value;
+
+enum DocumentOpType {
+    Get,
+    Put,
+    Append,
+    Delete,
+    Increment,
+}
+
+// Element of the parent container addressed by the last path segment.
+enum Target {
+    Key(Value),
+    Index(i64),
+}
+
+struct DocumentOp {
+    op: DocumentOpType,
+    ctx: Vec<CdtContext>,
+    target: Option<Target>,
+    value: Value,
+}
+
+/// Collection of document operations on a single CDT bin. Call `operations()` to get the list of
+/// operations to pass to `Client::operate`. If more than one operation returns a result, the bin
+/// value of the resulting record is a list containing the results in operation order.
+pub struct DocumentOps {
+    bin: String,
+    map_policy: MapPolicy,
+    list_policy: ListPolicy,
+    ops: Vec<DocumentOp>,
+}
+
+impl DocumentOps {
+    /// Create a new, empty set of document operations on the given bin, using the default map
+    /// and list policies.
+    pub fn new(bin: &str) -> Self {
+        DocumentOps {
+            bin: bin.to_string(),
+            map_policy: MapPolicy::default(),
+            list_policy: ListPolicy::default(),
+            ops: vec![],
+        }
+    }
+
+    /// Set the map policy used for put and increment operations on map elements.
+    pub fn map_policy(&mut self, policy: MapPolicy) -> &mut Self {
+        self.map_policy = policy;
+        self
+    }
+
+    /// Set the list policy used for append and increment operations on list elements.
+    pub fn list_policy(&mut self, policy: ListPolicy) -> &mut Self {
+        self.list_policy = policy;
+        self
+    }
+
+    /// Read the element at the given path.
+    pub fn get(&mut self, path: &str) -> Result<&mut Self> {
+        self.push(DocumentOpType::Get, path, Value::Nil)
+    }
+
+    /// Write the element at the given path. Map elements are created if they do not exist; list
+    /// elements are overwritten.
+    pub fn put<V: Into<Value>>(&mut self, path: &str, value: V) -> Result<&mut Self> {
+        let value = value.into();
+        if value.is_nil() {
+            bail!(ErrorKind::InvalidArgument(
+                "Cannot put nil value; use delete instead".to_string()
+            ));
+        }
+        self.push(DocumentOpType::Put, path, value)
+    }
+
+    /// Append a value to the list at the given path.
+    pub fn append<V: Into<Value>>(&mut self, path: &str, value: V) -> Result<&mut Self> {
+        self.push(DocumentOpType::Append, path, value.into())
+    }
+
+    /// Remove the element at the given path.
+    pub fn delete(&mut self, path: &str) -> Result<&mut Self> {
+        self.push(DocumentOpType::Delete, path, Value::Nil)
+    }
+
+    /// Increment the number at the given path. List elements can only be incremented by
+    /// integers.
+    pub fn increment<V: Into<Value>>(&mut self, path: &str, value: V) -> Result<&mut Self> {
+        self.push(DocumentOpType::Increment, path, value.into())
+    }
+
+    /// Compile the document operations into operations for `Client::operate`.
+    pub fn operations(&self) -> Vec<Operation<'_>> {
+        self.ops.iter().map(|op| self.operation(op)).collect()
+    }
+
+    fn push(&mut self, op: DocumentOpType, path: &str, value: Value) -> Result<&mut Self> {
+        let mut ctx = ctx_from_path(path)?;
+        let target = match op {
+            DocumentOpType::Append => None,
+            _ => match ctx.pop() {
+                Some(last) if last.id == CtxType::MapKey as u8 => Some(Target::Key(last.value)),
+                Some(CdtContext {
+                    value: Value::Int(index),
+                    ..
+                }) => Some(Target::Index(index)),
+                _ => bail!(ErrorKind::InvalidArgument(format!(
+                    "Document path '{}' does not address an element",
+                    path
+                ))),
+            },
+        };
+        if let (DocumentOpType::Increment, Some(Target::Index(_))) = (&op, &target) {
+            if !matches!(value, Value::Int(_)) {
+                bail!(ErrorKind::InvalidArgument(format!(
+                    "List element at '{}' can only be incremented by an integer",
+                    path
+                )));
+            }
+        }
+        self.ops.push(DocumentOp {
+            op,
+            ctx,
+            target,
+            value,
+        });
+        Ok(self)
+    }
+
+    fn operation<'a>(&'a self, op: &'a DocumentOp) -> Operation<'a> {
+        let bin = self.bin.as_str();
+        let operation = match (&op.op, &op.target) {
+            (DocumentOpType::Append, _) => lists::append(&self.list_policy, bin, &op.value),
+            (DocumentOpType::Get, Some(Target::Key(key))) => {
+                maps::get_by_key(bin, key, MapReturnType::Value)
+            }
+            (DocumentOpType::Get, Some(Target::Index(index))) => lists::get(bin, *index),
+            (DocumentOpType::Put, Some(Target::Key(key))) => {
+                maps::put(&self.map_policy, bin, key, &op.value)
+            }
+            (DocumentOpType::Put, Some(Target::Index(index))) => lists::set(bin, *index, &op.value),
+            (DocumentOpType::Delete, Some(Target::Key(key))) => {
+                maps::remove_by_key(bin, key, MapReturnType::None)
+            }
+            (DocumentOpType::Delete, Some(Target::Index(index))) => {
+                lists::remove_by_index(bin, *index, lists::ListReturnType::None)
+            }
+            (DocumentOpType::Increment, Some(Target::Key(key))) => {
+                maps::increment_value(&self.map_policy, bin, key, &op.value)
+            }
+            (DocumentOpType::Increment, Some(Target::Index(index))) => {
+                let incr = match op.value {
+                    Value::Int(incr) => incr,
+                    _ => unreachable!(),
+                };
+                lists::increment(&self.list_policy, bin, *index, incr)
+            }
+            (_, None) => unreachable!(),
+        };
+        operation.set_context(&op.ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DocumentOps;
+    use crate::operations::{OperationBin, OperationType};
+    use crate::Value;
+
+    #[test]
+    fn operations() {
+        let mut doc = DocumentOps::new("doc");
+        doc.get("a.b[1]")
+            .unwrap()
+            .put("a.c", 1)
+            .unwrap()
+            .append("a.list", "x")
+            .unwrap()
+            .delete("$[0]")
+            .unwrap()
+            .increment("a.n", 1.5)
+            .unwrap();
+
+        let ops = doc.operations();
+        assert_eq!(ops.len(), 5);
+        let ctx_len: Vec<usize> = ops.iter().map(|op| op.ctx.len()).collect();
+        assert_eq!(ctx_len, vec![2, 1, 2, 0, 1]);
+        assert!(matches!(ops[0].op, OperationType::CdtRead));
+        assert!(ops[1..]
+            .iter()
+            .all(|op| matches!(op.op, OperationType::CdtWrite)));
+        assert!(ops
+            .iter()
+            .all(|op| matches!(op.bin, OperationBin::Name("doc"))));
+    }
+
+    #[test]
+    fn invalid_operations() {
+        let mut doc = DocumentOps::new("doc");
+        assert!(doc.get("").is_err());
+        assert!(doc.get("a..b").is_err());
+        assert!(doc.put("a", Value::Nil).is_err());
+        assert!(doc.increment("a[0]", 1.5).is_err());
+        assert!(doc.operations().is_empty());
+    }
+}
@@ -295,7 +295,12 @@ pub fn put_items<'a>(
     bin: &'a str,
     items: &'a HashMap<Value, Value>,
 ) -> Operation<'a> {
-    let mut args = vec![CdtArgument::Map(items)];
+    // key-ordered maps are written in key order, so that the server does not have to sort them
+    let items = match policy.order {
+        MapOrder::Unordered => CdtArgument::Map(items),
+        MapOrder::KeyOrdered | MapOrder::KeyValueOrdered => CdtArgument::OrderedMap(items),
+    };
+    let mut args = vec![items];
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
     }
@@ -19,6 +19,7 @@ pub mod bitwise;
 #[doc(hidden)]
 pub mod cdt;
 pub mod cdt_context;
+pub mod document;
 pub mod exp;
 pub mod hll;
 pub mod lists;
@@ -95,7 +96,7 @@ pub struct Operation<'a> {
 
 impl<'a> Operation<'a> {
     #[doc(hidden)]
-    pub fn estimate_size(&self) -> Result<usize> {
+    pub fn estimate_size(&self, buffer: &Buffer) -> Result<usize> {
         let mut size: usize = 0;
         size += match self.bin {
             OperationBin::Name(bin) => bin.len(),
@@ -103,7 +104,7 @@ impl<'a> Operation<'a> {
         };
         size += match self.data {
             OperationData::None => 0,
-            OperationData::Value(value) => value.estimate_size()?,
+            OperationData::Value(value) => buffer.estimate_value_size(value)?,
             OperationData::EXPOp(ref exp_op) => exp_op.estimate_size()?,
             OperationData::CdtListOp(ref cdt_op)
             | OperationData::CdtMapOp(ref cdt_op)
@@ -119,7 +120,7 @@ impl<'a> Operation<'a> {
         let mut size: usize = 0;
 
         // remove the header size from the estimate
-        let op_size = self.estimate_size()?;
+        let op_size = self.estimate_size(buffer)?;
 
         size += buffer.write_u32(op_size as u32 + 4)?;
         size += buffer.write_u8(self.op as u8)?;
@@ -129,8 +130,9 @@ impl<'a> Operation<'a> {
                 size += self.write_op_header_to(buffer, ParticleType::NULL as u8)?;
             }
             OperationData::Value(value) => {
-                size += self.write_op_header_to(buffer, value.particle_type() as u8)?;
-                size += value.write_to(buffer)?;
+                let particle_type = buffer.value_particle_type(value);
+                size += self.write_op_header_to(buffer, particle_type as u8)?;
+                size += buffer.write_value(value)?;
             }
             OperationData::CdtListOp(ref cdt_op)
             | OperationData::CdtMapOp(ref cdt_op)
@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::commands::admin_command::AdminCommand;
 use crate::errors::Result;
+use crate::interceptor::Interceptor;
+use crate::metrics::MetricsListener;
+use crate::transport::{TcpTransport, Transport};
 
 /// `ClientPolicy` encapsulates parameters for client policy command.
 #[derive(Debug, Clone)]
@@ -30,12 +34,30 @@ pub struct ClientPolicy {
 
     /// Connection idle timeout. Every time a connection is used, its idle
     /// deadline will be extended by this duration. When this deadline is reached,
-    /// the connection will be closed and discarded from the connection pool.
+    /// the connection will be closed and discarded from the connection pool, unless the pool
+    /// holds no more than `min_conns_per_node` connections.
     pub idle_timeout: Option<Duration>,
 
     /// Maximum number of synchronous connections allowed per server node.
     pub max_conns_per_node: usize,
 
+    /// Minimum number of synchronous connections kept open per server node. The connections are
+    /// opened when a node is added to the cluster, and re-opened during cluster tend if
+    /// connections had to be closed. The minimum connections are exempt from `idle_timeout`; idle
+    /// connections above the minimum are closed during tend. If set, the server's
+    /// `proto-fd-idle-ms` should be 0, so that the server does not close the minimum connections.
+    pub min_conns_per_node: usize,
+
+    /// Maximum time to wait for a connection when all connections to a node are in use. Waiting
+    /// callers are served in the order they arrived; the wait is bounded by the socket timeout of
+    /// the command. If not set, commands fail with `NoMoreConnections` right away when the
+    /// connection pool is exhausted.
+    pub conn_acquire_timeout: Option<Duration>,
+
+    /// Maximum number of callers waiting for a connection to a node. Further callers fail with
+    /// `NoMoreConnections` right away. Only used if `conn_acquire_timeout` is set.
+    pub max_conn_waiters: usize,
+
     /// Number of connection pools used for each node. Machines with 8 CPU cores or less usually
     /// need only one connection pool per node. Machines with larger number of CPU cores may have
     /// their performance limited by contention for pooled connections. Contention for pooled
@@ -56,6 +78,16 @@ pub struct ClientPolicy {
     /// Minimum possible interval is 10 Milliseconds.
     pub tend_interval: Duration,
 
+    /// Maximum number of errors allowed per node within `error_rate_window`. Once the limit is
+    /// exceeded, attempts to send a command to the node fail with a `MaxErrorRate` error until
+    /// the window resets, giving an overloaded node time to recover; the command is retried like
+    /// after a network error. Errors counted are network errors, socket timeouts and
+    /// `DeviceOverload` server errors. Default: 0 (disabled).
+    pub max_error_rate: usize,
+
+    /// Number of tend intervals after which the error count of each node is reset.
+    pub error_rate_window: usize,
+
     /// A IP translation table is used in cases where different clients
     /// use different server IP addresses.  This may be necessary when
     /// using clients from both inside and outside a local area
@@ -83,6 +115,17 @@ pub struct ClientPolicy {
     /// to join the client's view of the cluster. Should only be set when connecting to servers
     /// that support the "cluster-name" info command.
     pub cluster_name: Option<String>,
+
+    /// Listener that receives the latency and outcome of every command, e.g.
+    /// `metrics::LatencyMetrics`. Default is no listener.
+    pub metrics_listener: Option<Arc<dyn MetricsListener>>,
+
+    /// Interceptors invoked before and after every command, in the order they are listed.
+    /// Default is no interceptors.
+    pub interceptors: Vec<Arc<dyn Interceptor>>,
+
+    /// Transport used to open the connections to the cluster nodes. Defaults to TCP.
+    pub transport: Arc<dyn Transport>,
 }
 
 impl Default for ClientPolicy {
@@ -92,14 +135,22 @@ impl Default for ClientPolicy {
             timeout: Some(Duration::new(30, 0)),
             idle_timeout: Some(Duration::new(5, 0)),
             max_conns_per_node: 256,
+            min_conns_per_node: 0,
+            conn_acquire_timeout: None,
+            max_conn_waiters: 256,
             conn_pools_per_node: 1,
             fail_if_not_connected: true,
             tend_interval: Duration::new(1, 0),
+            max_error_rate: 0,
+            error_rate_window: 1,
             ip_map: None,
             use_services_alternate: false,
             thread_pool_size: 128,
             cluster_name: None,
             buffer_reclaim_threshold: 65536,
+            metrics_listener: None,
+            interceptors: vec![],
+            transport: Arc::new(TcpTransport),
         }
     }
 }
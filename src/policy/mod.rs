@@ -28,6 +28,7 @@ mod priority;
 mod query_policy;
 mod read_policy;
 mod record_exists_action;
+mod retry_policy;
 mod scan_policy;
 mod write_policy;
 
@@ -43,11 +44,13 @@ pub use self::priority::Priority;
 pub use self::query_policy::QueryPolicy;
 pub use self::read_policy::ReadPolicy;
 pub use self::record_exists_action::RecordExistsAction;
+pub use self::retry_policy::{ExponentialBackoff, RetryContext, RetryPolicy};
 pub use self::scan_policy::ScanPolicy;
 pub use self::write_policy::WritePolicy;
 
 use crate::expressions::FilterExpression;
 use std::option::Option;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Trait implemented by most policy types; policies that implement this trait typically encompass
@@ -57,17 +60,31 @@ pub trait Policy {
     fn priority(&self) -> &Priority;
 
     #[doc(hidden)]
-    /// Deadline for current transaction based on specified timeout. For internal use only.
+    /// Deadline for current transaction based on specified total timeout. For internal use only.
     fn deadline(&self) -> Option<Instant>;
 
-    /// Total transaction timeout for both client and server. The timeout is tracked on the client
-    /// and also sent to the server along with the transaction in the wire protocol. The client
-    /// will most likely timeout first, but the server has the capability to timeout the
-    /// transaction as well.
+    /// Total transaction timeout for both client and server, including all retries. The timeout
+    /// is tracked on the client and the remaining time is also sent to the server along with the
+    /// transaction in the wire protocol. The client will most likely timeout first, but the server
+    /// has the capability to timeout the transaction as well.
     ///
-    /// The timeout is also used as a socket timeout. Default: 0 (no timeout).
+    /// Unless a socket timeout is set, the remaining time is also used as the socket timeout.
+    /// Default: 0 (no timeout).
     fn timeout(&self) -> Option<Duration>;
 
+    /// Total transaction timeout, including all retries. Same as `timeout`.
+    fn total_timeout(&self) -> Option<Duration> {
+        self.timeout()
+    }
+
+    /// Socket idle timeout for a single attempt of the transaction. If a socket read or write
+    /// times out, the transaction is retried as long as the total timeout has not been exceeded;
+    /// writes are only retried if a retry policy allows it. The socket timeout is capped by the
+    /// remaining total timeout. Default: 0 (no timeout).
+    fn socket_timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Maximum number of retries before aborting the current transaction. A retry may be attempted
     /// when there is a network error. If `max_retries` is exceeded, the abort will occur even if
     /// the timeout has not yet been exceeded.
@@ -76,6 +93,12 @@ pub trait Policy {
     /// Time to sleep between retries. Set to zero to skip sleep. Default: 500ms.
     fn sleep_between_retries(&self) -> Option<Duration>;
 
+    /// Retry policy that replaces `sleep_between_retries` if set. `max_retries` still caps the
+    /// number of retries.
+    fn retry_policy(&self) -> Option<&dyn RetryPolicy> {
+        None
+    }
+
     /// How replicas should be consulted in read operations to provide the desired consistency
     /// guarantee.
     fn consistency_level(&self) -> &ConsistencyLevel;
@@ -108,6 +131,10 @@ where
         self.base().timeout()
     }
 
+    fn socket_timeout(&self) -> Option<Duration> {
+        self.base().socket_timeout()
+    }
+
     fn max_retries(&self) -> Option<usize> {
         self.base().max_retries()
     }
@@ -115,6 +142,10 @@ where
     fn sleep_between_retries(&self) -> Option<Duration> {
         self.base().sleep_between_retries()
     }
+
+    fn retry_policy(&self) -> Option<&dyn RetryPolicy> {
+        self.base().retry_policy()
+    }
 }
 
 /// Common parameters shared by all policy types.
@@ -130,14 +161,21 @@ pub struct BasePolicy {
     /// read operation.
     pub consistency_level: ConsistencyLevel,
 
-    /// Timeout specifies transaction timeout.
-    /// This timeout is used to set the socket timeout and is also sent to the
-    /// server along with the transaction in the wire protocol.
+    /// Timeout specifies the total transaction timeout, including all retries.
+    /// The remaining time is sent to the server along with the transaction in the
+    /// wire protocol, and is used as the socket timeout unless `socket_timeout` is set.
     /// Default to no timeout (0).
     pub timeout: Option<Duration>,
 
+    /// SocketTimeout specifies the socket read and write timeout of a single attempt.
+    /// If the socket times out, the transaction is retried until the total timeout or
+    /// the maximum number of retries is reached; writes are only retried if a retry
+    /// policy allows it. The socket timeout is capped by the remaining total timeout.
+    /// Default to no timeout (0), i.e. only the total timeout applies.
+    pub socket_timeout: Option<Duration>,
+
     /// MaxRetries determines maximum number of retries before aborting the current transaction.
-    /// A retry is attempted when there is a network error other than timeout.
+    /// A retry is attempted when there is a network error or a socket timeout of a read.
     /// If maxRetries is exceeded, the abort will occur even if the timeout
     /// has not yet been exceeded.
     pub max_retries: Option<usize>,
@@ -146,6 +184,11 @@ pub struct BasePolicy {
     /// transaction fails and the timeout was not exceeded.  Enter zero to skip sleep.
     pub sleep_between_retries: Option<Duration>,
 
+    /// Optional retry policy deciding whether and when a failed transaction is retried, e.g.
+    /// `ExponentialBackoff`. If set, `sleep_between_retries` is ignored, and `max_retries`
+    /// remains a hard limit on top of the limit of the retry policy. Default to none.
+    pub retry_policy: Option<Arc<dyn RetryPolicy>>,
+
     /// Optional FilterExpression
     pub filter_expression: Option<FilterExpression>,
 }
@@ -166,6 +209,10 @@ impl Policy for BasePolicy {
         self.timeout
     }
 
+    fn socket_timeout(&self) -> Option<Duration> {
+        self.socket_timeout
+    }
+
     fn max_retries(&self) -> Option<usize> {
         self.max_retries
     }
@@ -174,6 +221,10 @@ impl Policy for BasePolicy {
         self.sleep_between_retries
     }
 
+    fn retry_policy(&self) -> Option<&dyn RetryPolicy> {
+        self.retry_policy.as_deref()
+    }
+
     fn consistency_level(&self) -> &ConsistencyLevel {
         &self.consistency_level
     }
@@ -27,8 +27,10 @@ impl Default for ReadPolicy {
         ReadPolicy {
             priority: Priority::Default,
             timeout: Some(Duration::new(30, 0)),
+            socket_timeout: None,
             max_retries: Some(2),
             sleep_between_retries: Some(Duration::new(0, 500_000_000)),
+            retry_policy: None,
             consistency_level: ConsistencyLevel::ConsistencyOne,
             filter_expression: None,
         }
@@ -0,0 +1,261 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::cluster::Node;
+use crate::errors::{Error, ErrorKind};
+use crate::ResultCode;
+
+/// Information about a failed command attempt, passed to a `RetryPolicy` to decide whether the
+/// command should be retried.
+#[derive(Debug)]
+pub struct RetryContext<'a> {
+    /// Number of attempts made so far, including the failed one. Starts at 1.
+    pub attempt: usize,
+
+    /// Error returned by the failed attempt.
+    pub error: &'a Error,
+
+    /// Node the failed attempt was sent to, if a node could be selected.
+    pub node: Option<&'a Arc<Node>>,
+
+    /// Whether the command modifies the record.
+    pub is_write: bool,
+
+    /// Whether the failed attempt was sent to the server, i.e. a write might have been applied.
+    pub sent: bool,
+}
+
+/// Decides whether, and after what delay, a failed command is retried.
+///
+/// A retry policy set on a `BasePolicy` replaces the `sleep_between_retries` setting of that
+/// policy. The `max_retries` and `timeout` of the policy still apply; set `max_retries` to `None`
+/// to let the retry policy alone decide how often a command is retried.
+pub trait RetryPolicy: fmt::Debug + Send + Sync {
+    /// Returns the delay before the next attempt, or `None` if the command should fail with the
+    /// error of the last attempt.
+    fn retry_delay(&self, ctx: &RetryContext) -> Option<Duration>;
+}
+
+/// Retry policy with exponential backoff and jitter.
+///
+/// Network errors are always retried; server errors are retried only if their result code is
+/// listed in `retry_on`.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    /// Maximum number of retries after the first attempt. Default: 2.
+    pub max_retries: usize,
+
+    /// Delay before the first retry. Default: 10ms.
+    pub initial_delay: Duration,
+
+    /// Upper bound for the delay between retries. Default: 1s.
+    pub max_delay: Duration,
+
+    /// Factor the delay is multiplied with after every retry. Default: 2.
+    pub multiplier: f64,
+
+    /// Fraction of the delay, between 0 and 1, that is randomized to spread out retries from
+    /// concurrent clients. A jitter of 0.5 yields delays between 50% and 100% of the backoff.
+    /// Default: 0.5.
+    pub jitter: f64,
+
+    /// Server result codes that are retried. Default: `KeyBusy`, `DeviceOverload`,
+    /// `ServerNotAvailable` and `Timeout`.
+    pub retry_on: Vec<ResultCode>,
+
+    /// Treat writes as non-idempotent: a write is not retried once it has been sent to the
+    /// server, since the failed attempt might have been applied. Default: false.
+    pub non_idempotent_writes: bool,
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        ExponentialBackoff {
+            max_retries: 2,
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: 0.5,
+            retry_on: vec![
+                ResultCode::KeyBusy,
+                ResultCode::DeviceOverload,
+                ResultCode::ServerNotAvailable,
+                ResultCode::Timeout,
+            ],
+            non_idempotent_writes: false,
+        }
+    }
+}
+
+impl ExponentialBackoff {
+    /// Returns the backoff before the given retry, without jitter. The first retry is 1. A negative
+    /// or NaN multiplier is treated as 0.
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let exp = retry.saturating_sub(1).min(i32::MAX as usize) as i32;
+        let multiplier = self.multiplier.max(0.0);
+        let delay = self.initial_delay.as_secs_f64() * multiplier.powi(exp);
+        if delay.is_finite() && delay < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_delay
+        }
+    }
+
+    fn is_retryable(&self, err: &Error) -> bool {
+        match *err.kind() {
+            ErrorKind::ServerError(result_code) => self.retry_on.contains(&result_code),
+            _ => err.is_retryable(),
+        }
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn retry_delay(&self, ctx: &RetryContext) -> Option<Duration> {
+        if ctx.attempt > self.max_retries || !self.is_retryable(ctx.error) {
+            return None;
+        }
+        if self.non_idempotent_writes && ctx.is_write && ctx.sent {
+            return None;
+        }
+
+        let backoff = self.backoff(ctx.attempt);
+        let jitter = if self.jitter.is_nan() {
+            0.0
+        } else {
+            self.jitter.max(0.0).min(1.0)
+        };
+        if jitter == 0.0 {
+            return Some(backoff);
+        }
+        let factor = 1.0 - jitter * rand::thread_rng().gen::<f64>();
+        Some(backoff.mul_f64(factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(attempt: usize, error: &Error, is_write: bool, sent: bool) -> RetryContext<'_> {
+        RetryContext {
+            attempt,
+            error,
+            node: None,
+            is_write,
+            sent,
+        }
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = ExponentialBackoff {
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            ..ExponentialBackoff::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff(4), Duration::from_millis(50));
+        assert_eq!(policy.backoff(1000), Duration::from_millis(50));
+
+        let policy = ExponentialBackoff {
+            multiplier: -2.0,
+            ..ExponentialBackoff::default()
+        };
+        assert_eq!(policy.backoff(1), policy.initial_delay);
+        assert_eq!(policy.backoff(2), Duration::from_secs(0));
+
+        let policy = ExponentialBackoff {
+            multiplier: f64::NAN,
+            ..ExponentialBackoff::default()
+        };
+        assert_eq!(policy.backoff(2), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn retry_delay() {
+        let policy = ExponentialBackoff {
+            max_retries: 3,
+            ..ExponentialBackoff::default()
+        };
+        let err: Error = ErrorKind::Connection("reset".into()).into();
+        for attempt in 1..=3 {
+            let delay = policy
+                .retry_delay(&context(attempt, &err, false, true))
+                .unwrap();
+            let backoff = policy.backoff(attempt);
+            assert!(delay <= backoff);
+            assert!(delay >= backoff / 2);
+        }
+        assert_eq!(policy.retry_delay(&context(4, &err, false, true)), None);
+
+        let policy = ExponentialBackoff {
+            jitter: 0.0,
+            ..ExponentialBackoff::default()
+        };
+        assert_eq!(
+            policy.retry_delay(&context(1, &err, false, true)),
+            Some(policy.initial_delay)
+        );
+
+        let policy = ExponentialBackoff {
+            jitter: f64::NAN,
+            ..ExponentialBackoff::default()
+        };
+        assert_eq!(
+            policy.retry_delay(&context(1, &err, false, true)),
+            Some(policy.initial_delay)
+        );
+    }
+
+    #[test]
+    fn retry_on_result_codes() {
+        let policy = ExponentialBackoff {
+            retry_on: vec![ResultCode::KeyBusy],
+            ..ExponentialBackoff::default()
+        };
+        let busy: Error = ErrorKind::ServerError(ResultCode::KeyBusy).into();
+        let not_found: Error = ErrorKind::ServerError(ResultCode::KeyNotFoundError).into();
+        let invalid: Error = ErrorKind::InvalidArgument("bad".into()).into();
+        assert!(policy
+            .retry_delay(&context(1, &busy, false, true))
+            .is_some());
+        assert!(policy
+            .retry_delay(&context(1, &not_found, false, true))
+            .is_none());
+        assert!(policy
+            .retry_delay(&context(1, &invalid, false, false))
+            .is_none());
+    }
+
+    #[test]
+    fn non_idempotent_writes() {
+        let policy = ExponentialBackoff {
+            non_idempotent_writes: true,
+            ..ExponentialBackoff::default()
+        };
+        let err: Error = ErrorKind::Connection("reset".into()).into();
+        assert!(policy.retry_delay(&context(1, &err, true, false)).is_some());
+        assert!(policy.retry_delay(&context(1, &err, true, true)).is_none());
+        assert!(policy.retry_delay(&context(1, &err, false, true)).is_some());
+    }
+}
@@ -43,7 +43,8 @@ pub struct ScanPolicy {
 
     /// Maximum time in milliseconds to wait when polling socket for availability prior to
     /// performing an operation on the socket on the server side. Zero means there is no socket
-    /// timeout. Default: 10,000 ms.
+    /// timeout. Only used while `base_policy.socket_timeout` is not set. Default: 10,000 ms.
+    #[deprecated(note = "use `base_policy.socket_timeout` instead")]
     pub socket_timeout: u32,
 
     /// Optional Filter Expression
@@ -63,6 +64,7 @@ impl ScanPolicy {
 }
 
 impl Default for ScanPolicy {
+    #[allow(deprecated)]
     fn default() -> Self {
         ScanPolicy {
             base_policy: BasePolicy::default(),
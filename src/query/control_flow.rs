@@ -0,0 +1,32 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+/// Returned by the callbacks of callback-driven scans and queries to tell the client whether to
+/// continue with the next record or to stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlFlow {
+    /// Continue with the next record.
+    Continue,
+
+    /// Stop the scan or query and abort it on all nodes.
+    Break,
+}
+
+impl ControlFlow {
+    /// Returns `true` if this is `ControlFlow::Break`.
+    pub fn is_break(&self) -> bool {
+        *self == ControlFlow::Break
+    }
+}
@@ -16,12 +16,15 @@
 //! Types and methods used for database queries and scans.
 #![allow(clippy::missing_errors_doc)]
 
+pub use self::control_flow::ControlFlow;
 pub use self::filter::Filter;
 pub use self::index_types::{CollectionIndexType, IndexType};
+pub(crate) use self::recordset::JobType;
 pub use self::recordset::Recordset;
 pub use self::statement::Statement;
 pub use self::udf::UDFLang;
 
+mod control_flow;
 mod filter;
 mod index_types;
 mod recordset;
@@ -16,18 +16,44 @@
 extern crate rand;
 
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 
 use crossbeam_queue::SegQueue;
 use rand::Rng;
 
-use crate::errors::Result;
-use crate::Record;
+use crate::cluster::Node;
+use crate::errors::{ErrorKind, Result};
+use crate::{Record, ResultCode};
 
-/// Virtual collection of records retrieved through queries and scans. During a query/scan,
-/// multiple threads will retrieve records from the server nodes and put these records on an
-/// internal queue managed by the recordset. The single user thread consumes these records from the
-/// queue.
+// Maximum time to wait for a node to acknowledge the abort of a job.
+const ABORT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Type of the server-side job feeding a recordset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobType {
+    Scan,
+    Query,
+}
+
+impl JobType {
+    // Error returned to the node workers once the recordset has been closed by the caller.
+    pub const fn abort_error(self) -> ErrorKind {
+        match self {
+            JobType::Scan => ErrorKind::ServerError(ResultCode::ScanAbort),
+            JobType::Query => ErrorKind::ServerError(ResultCode::QueryAborted),
+        }
+    }
+}
+
+/// Virtual collection of records retrieved through queries and scans.
+///
+/// During a query/scan, multiple threads will retrieve records from the server nodes and put
+/// these records on an internal queue. The caller consumes these records from the queue.
+///
+/// Closing or dropping the recordset before all records have been consumed aborts the query/scan
+/// on all participating server nodes.
 pub struct Recordset {
     instances: AtomicUsize,
     record_queue_count: AtomicUsize,
@@ -35,27 +61,36 @@ pub struct Recordset {
     record_queue: SegQueue<Result<Record>>,
     active: AtomicBool,
     task_id: AtomicUsize,
+    job_type: JobType,
+    nodes: Vec<Arc<Node>>,
+    // whether the job has finished on the node at the same index in `nodes`
+    finished: Vec<AtomicBool>,
 }
 
 impl Recordset {
-    #[doc(hidden)]
-    pub fn new(rec_queue_size: usize, nodes: usize) -> Self {
+    pub(crate) fn new(rec_queue_size: usize, nodes: Vec<Arc<Node>>, job_type: JobType) -> Self {
         let mut rng = rand::thread_rng();
         let task_id = rng.gen::<usize>();
 
         Recordset {
-            instances: AtomicUsize::new(nodes),
+            instances: AtomicUsize::new(nodes.len()),
             record_queue_size: AtomicUsize::new(rec_queue_size),
             record_queue_count: AtomicUsize::new(0),
             record_queue: SegQueue::new(),
             active: AtomicBool::new(true),
             task_id: AtomicUsize::new(task_id),
+            job_type,
+            finished: nodes.iter().map(|_| AtomicBool::new(false)).collect(),
+            nodes,
         }
     }
 
-    /// Close the query.
+    /// Close the query. If the query is still running, it is aborted on all participating server
+    /// nodes it has not finished on yet, and the connections streaming its records are closed.
     pub fn close(&self) {
-        self.active.store(false, Ordering::Relaxed)
+        if self.active.swap(false, Ordering::Relaxed) {
+            Recordset::abort_job(self.job_type, self.task_id(), &self.running_nodes());
+        }
     }
 
     /// Check whether the query is still active.
@@ -83,9 +118,66 @@ impl Recordset {
     #[doc(hidden)]
     pub fn signal_end(&self) {
         if self.instances.fetch_sub(1, Ordering::Relaxed) == 1 {
-            self.close()
+            self.active.store(false, Ordering::Relaxed);
         };
     }
+
+    // Signals that the job has finished on the given node.
+    pub(crate) fn signal_node_end(&self, node: &Node) {
+        if let Some(i) = self.nodes.iter().position(|n| n.name() == node.name()) {
+            self.finished[i].store(true, Ordering::Relaxed);
+        }
+        self.signal_end();
+    }
+
+    pub(crate) const fn job_type(&self) -> JobType {
+        self.job_type
+    }
+
+    // Nodes the job has not finished on yet.
+    fn running_nodes(&self) -> Vec<Arc<Node>> {
+        self.nodes
+            .iter()
+            .zip(&self.finished)
+            .filter(|(_, finished)| !finished.load(Ordering::Relaxed))
+            .map(|(node, _)| node.clone())
+            .collect()
+    }
+
+    fn abort_job(job_type: JobType, task_id: u64, nodes: &[Arc<Node>]) {
+        let cmd = match job_type {
+            JobType::Scan => format!("scan-abort:id={}", task_id),
+            JobType::Query => format!("query-kill:trid={}", task_id),
+        };
+
+        for node in nodes {
+            // The job may already have completed on some of the nodes.
+            let timeout = node
+                .client_policy()
+                .timeout
+                .map_or(ABORT_TIMEOUT, |timeout| timeout.min(ABORT_TIMEOUT));
+            match node.info(Some(timeout), &[&cmd]) {
+                Ok(response) => debug!("Node {}: {} => {:?}", node, cmd, response),
+                Err(err) => warn!("Node {}: failed to abort job {}: {}", node, cmd, err),
+            }
+        }
+    }
+}
+
+impl Drop for Recordset {
+    // The job is aborted on a background thread, so that dropping the recordset does not block
+    // on the round trips to the nodes.
+    fn drop(&mut self) {
+        if !self.active.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let nodes = self.running_nodes();
+        if nodes.is_empty() {
+            return;
+        }
+        let (job_type, task_id) = (self.job_type, self.task_id());
+        thread::spawn(move || Recordset::abort_job(job_type, task_id, &nodes));
+    }
 }
 
 impl<'a> Iterator for &'a Recordset {
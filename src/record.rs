@@ -14,12 +14,14 @@
 // the License.
 
 #[cfg(feature = "serialization")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
 use std::fmt;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::errors;
+use crate::mapping::{self, FromValue};
 use crate::Key;
 use crate::Value;
 
@@ -30,7 +32,7 @@ lazy_static! {
 
 /// Container object for a database record.
 #[derive(Debug)]
-#[cfg_attr(feature = "serialization", derive(Serialize))]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub struct Record {
     /// Record key. When reading a record from the database, the key is not set in the returned
     /// Record struct.
@@ -63,19 +65,45 @@ impl Record {
         }
     }
 
+    /// Returns the value of the named bin, converted to the requested type. A missing bin is
+    /// treated as `nil`, i.e. it can be read as `Option<T>`. Returns an `InvalidValue` error if
+    /// the bin value cannot be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// # use std::collections::HashMap;
+    /// # let mut bins = HashMap::new();
+    /// # bins.insert("count".to_string(), as_val!(42));
+    /// # let record = Record::new(None, bins, 1, 0);
+    /// let count = record.get::<i64>("count").unwrap();
+    /// let name = record.get::<Option<String>>("name").unwrap();
+    /// assert_eq!(count, 42);
+    /// assert_eq!(name, None);
+    /// ```
+    pub fn get<T: FromValue>(&self, name: &str) -> errors::Result<T> {
+        mapping::bin_value(self, name)
+    }
+
     /// Returns the remaining time-to-live (TTL, a.k.a. expiration time) for the record or `None`
     /// if the record never expires.
     pub fn time_to_live(&self) -> Option<Duration> {
-        match self.expiration {
-            0 => None,
-            secs_since_epoch => {
-                let expiration = *CITRUSLEAF_EPOCH + Duration::new(u64::from(secs_since_epoch), 0);
-                match expiration.duration_since(SystemTime::now()) {
-                    Ok(d) => Some(d),
-                    // Record was not expired at server but it looks expired at client
-                    // because of delay or clock difference, present it as not-expired.
-                    Err(_) => Some(Duration::new(1u64, 0)),
-                }
+        time_to_live(self.expiration)
+    }
+}
+
+// Converts an expiration time, in seconds since the Citrusleaf epoch, into the remaining TTL.
+pub fn time_to_live(expiration: u32) -> Option<Duration> {
+    match expiration {
+        0 => None,
+        secs_since_epoch => {
+            let expiration = *CITRUSLEAF_EPOCH + Duration::new(u64::from(secs_since_epoch), 0);
+            match expiration.duration_since(SystemTime::now()) {
+                Ok(d) => Some(d),
+                // Record was not expired at server but it looks expired at client
+                // because of delay or clock difference, present it as not-expired.
+                Err(_) => Some(Duration::new(1u64, 0)),
             }
         }
     }
@@ -130,4 +158,22 @@ mod tests {
         let record = Record::new(None, HashMap::new(), 0, 0);
         assert_eq!(record.time_to_live(), None);
     }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn serialization_round_trip() {
+        let key = as_key!("test", "demo", "someKey");
+        let mut bins = HashMap::new();
+        bins.insert("int".to_string(), as_val!(42));
+        bins.insert("blob".to_string(), as_blob!(vec![1u8, 2, 3]));
+        bins.insert("geo".to_string(), as_geo!(r#"{"type":"Point"}"#));
+        let record = Record::new(Some(key), bins, 3, 0x0d00_d21c);
+
+        let json = serde_json::to_string(&record).unwrap();
+        let restored: Record = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.key, record.key);
+        assert_eq!(restored.bins, record.bins);
+        assert_eq!(restored.generation, record.generation);
+        assert_eq!(restored.time_to_live(), record.time_to_live());
+    }
 }
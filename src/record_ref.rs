@@ -0,0 +1,485 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Borrowed views of records and bin values, decoded in place from the buffer of the connection
+//! streaming them. Used by `Client::scan_borrowed` and `Client::query_borrowed` to process
+//! records without allocating memory for each bin.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str;
+use std::time::Duration;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::commands::buffer::{Buffer, MSG_REMAINING_HEADER_SIZE};
+use crate::commands::field_type::FieldType;
+use crate::commands::ParticleType;
+use crate::errors::{ErrorKind, Result};
+use crate::msgpack::decoder;
+use crate::record;
+use crate::value::FloatValue;
+use crate::{Key, Record, Value};
+
+/// Bin value borrowed from the buffer it was decoded from. Lists and maps are not decoded; they
+/// hold the raw MessagePack encoded bytes, which can be decoded using `to_value`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueRef<'a> {
+    /// Empty value.
+    Nil,
+    /// Boolean value.
+    Bool(bool),
+    /// Integer value.
+    Int(i64),
+    /// Floating point value.
+    Float(f64),
+    /// String value.
+    String(&'a str),
+    /// Byte array value.
+    Blob(&'a [u8]),
+    /// GeoJSON data value.
+    GeoJSON(&'a str),
+    /// HyperLogLog value.
+    HLL(&'a [u8]),
+    /// MessagePack encoded list value.
+    List(&'a [u8]),
+    /// MessagePack encoded map value.
+    Map(&'a [u8]),
+}
+
+impl<'a> ValueRef<'a> {
+    pub(crate) fn from_particle(ptype: u8, bytes: &'a [u8]) -> Result<Self> {
+        let value = match ParticleType::try_from(ptype)? {
+            ParticleType::NULL => ValueRef::Nil,
+            ParticleType::BOOL => ValueRef::Bool(Reader::new(bytes).read_u8()? != 0),
+            ParticleType::INTEGER => {
+                ValueRef::Int(BigEndian::read_i64(Reader::new(bytes).read(8)?))
+            }
+            ParticleType::FLOAT => {
+                ValueRef::Float(BigEndian::read_f64(Reader::new(bytes).read(8)?))
+            }
+            ParticleType::STRING => ValueRef::String(str::from_utf8(bytes)?),
+            ParticleType::GEOJSON => {
+                let mut reader = Reader::new(bytes);
+                reader.read(1)?;
+                let ncells = reader.read_u16()? as usize;
+                reader.read(ncells * 8)?;
+                ValueRef::GeoJSON(str::from_utf8(reader.rest())?)
+            }
+            ParticleType::BLOB | ParticleType::DIGEST | ParticleType::LDT => ValueRef::Blob(bytes),
+            ParticleType::HLL => ValueRef::HLL(bytes),
+            ParticleType::LIST => ValueRef::List(bytes),
+            ParticleType::MAP => ValueRef::Map(bytes),
+        };
+        Ok(value)
+    }
+
+    /// Returns true if this value is the empty value (nil).
+    pub const fn is_nil(&self) -> bool {
+        matches!(*self, ValueRef::Nil)
+    }
+
+    /// Returns the boolean value, if this is a boolean.
+    pub const fn as_bool(&self) -> Option<bool> {
+        match *self {
+            ValueRef::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value, if this is an integer.
+    pub const fn as_i64(&self) -> Option<i64> {
+        match *self {
+            ValueRef::Int(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Returns the floating point value, if this is a float.
+    pub const fn as_f64(&self) -> Option<f64> {
+        match *self {
+            ValueRef::Float(f) => Some(f),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, if this is a string or GeoJSON value.
+    pub const fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            ValueRef::String(s) | ValueRef::GeoJSON(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw bytes, if this is a blob or HLL value.
+    pub const fn as_bytes(&self) -> Option<&'a [u8]> {
+        match *self {
+            ValueRef::Blob(b) | ValueRef::HLL(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Decodes the value into an owned `Value`.
+    pub fn to_value(&self) -> Result<Value> {
+        let value = match *self {
+            ValueRef::Nil => Value::Nil,
+            ValueRef::Bool(b) => Value::Bool(b),
+            ValueRef::Int(i) => Value::Int(i),
+            ValueRef::Float(f) => Value::Float(FloatValue::from(f)),
+            ValueRef::String(s) => Value::String(s.to_owned()),
+            ValueRef::Blob(b) => Value::Blob(b.to_vec()),
+            ValueRef::GeoJSON(s) => Value::GeoJSON(s.to_owned()),
+            ValueRef::HLL(b) => Value::HLL(b.to_vec()),
+            ValueRef::List(b) => decoder::unpack_value_list(&mut Self::buffer(b))?,
+            ValueRef::Map(b) => decoder::unpack_value_map(&mut Self::buffer(b))?,
+        };
+        Ok(value)
+    }
+
+    fn buffer(bytes: &[u8]) -> Buffer {
+        let mut buffer = Buffer::new(0);
+        buffer.data_buffer.extend_from_slice(bytes);
+        buffer
+    }
+}
+
+impl<'a> fmt::Display for ValueRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_value() {
+            Ok(value) => value.fmt(f),
+            Err(_) => write!(f, "<invalid value>"),
+        }
+    }
+}
+
+/// Record borrowed from the buffer it was decoded from. Bins are decoded lazily, each time they
+/// are accessed, without allocating memory.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordRef<'a> {
+    namespace: &'a str,
+    set_name: &'a str,
+    digest: [u8; 20],
+    user_key: Option<ValueRef<'a>>,
+    generation: u32,
+    expiration: u32,
+    op_count: usize,
+    ops: &'a [u8],
+}
+
+impl<'a> RecordRef<'a> {
+    // Parses a complete record message, starting with the message header. Bins are validated
+    // once here, so that iterating over them later cannot fail.
+    pub(crate) fn parse(buf: &'a [u8]) -> Result<Self> {
+        let mut reader = Reader::new(buf);
+        let header = reader.read(MSG_REMAINING_HEADER_SIZE as usize)?;
+        let generation = BigEndian::read_u32(&header[6..10]);
+        let expiration = BigEndian::read_u32(&header[10..14]);
+        let field_count = BigEndian::read_u16(&header[18..20]) as usize;
+        let op_count = BigEndian::read_u16(&header[20..22]) as usize;
+
+        let mut record = RecordRef {
+            namespace: "",
+            set_name: "",
+            digest: [0; 20],
+            user_key: None,
+            generation,
+            expiration,
+            op_count,
+            ops: &[],
+        };
+
+        for _ in 0..field_count {
+            let field_len = reader.read_u32()? as usize;
+            let field = reader.read(field_len)?;
+            if field.is_empty() {
+                bail!(ErrorKind::BadResponse("Empty record field".to_string()));
+            }
+            let data = &field[1..];
+            match field[0] {
+                x if x == FieldType::DigestRipe as u8 && data.len() == 20 => {
+                    record.digest.copy_from_slice(data);
+                }
+                x if x == FieldType::Namespace as u8 => record.namespace = str::from_utf8(data)?,
+                x if x == FieldType::Table as u8 => record.set_name = str::from_utf8(data)?,
+                x if x == FieldType::Key as u8 && !data.is_empty() => {
+                    record.user_key = Some(ValueRef::from_particle(data[0], &data[1..])?);
+                }
+                _ => (),
+            }
+        }
+
+        record.ops = reader.rest();
+        let mut bins = record.bins();
+        for _ in 0..op_count {
+            bins.read_bin()?;
+        }
+        Ok(record)
+    }
+
+    /// Namespace of the record.
+    pub const fn namespace(&self) -> &'a str {
+        self.namespace
+    }
+
+    /// Set name of the record.
+    pub const fn set_name(&self) -> &'a str {
+        self.set_name
+    }
+
+    /// Digest of the record key.
+    pub const fn digest(&self) -> &[u8; 20] {
+        &self.digest
+    }
+
+    /// User key of the record, if it was stored with the record.
+    pub const fn user_key(&self) -> Option<ValueRef<'a>> {
+        self.user_key
+    }
+
+    /// Record modification count.
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Returns the remaining time-to-live (TTL, a.k.a. expiration time) for the record or `None`
+    /// if the record never expires.
+    pub fn time_to_live(&self) -> Option<Duration> {
+        record::time_to_live(self.expiration)
+    }
+
+    /// Number of bins in the record.
+    pub const fn bin_count(&self) -> usize {
+        self.op_count
+    }
+
+    /// Returns the value of the named bin, or `None` if the record has no such bin.
+    pub fn get(&self, name: &str) -> Option<ValueRef<'a>> {
+        self.bins()
+            .find(|&(bin, _)| bin == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the names and values of the record's bins.
+    pub const fn bins(&self) -> BinsRef<'a> {
+        BinsRef {
+            reader: Reader::new(self.ops),
+            remaining: self.op_count,
+        }
+    }
+
+    /// Returns an owned copy of the record key.
+    pub fn key(&self) -> Result<Key> {
+        let user_key = match self.user_key {
+            Some(ref value) => Some(value.to_value()?),
+            None => None,
+        };
+        Ok(Key {
+            namespace: self.namespace.to_owned(),
+            set_name: self.set_name.to_owned(),
+            user_key,
+            digest: self.digest,
+        })
+    }
+
+    /// Decodes the record into an owned `Record`.
+    pub fn to_record(&self) -> Result<Record> {
+        let mut bins = HashMap::with_capacity(self.op_count);
+        for (name, value) in self.bins() {
+            bins.insert(name.to_owned(), value.to_value()?);
+        }
+        Ok(Record::new(
+            Some(self.key()?),
+            bins,
+            self.generation,
+            self.expiration,
+        ))
+    }
+}
+
+/// Iterator over the bins of a `RecordRef`.
+#[derive(Debug, Clone)]
+pub struct BinsRef<'a> {
+    reader: Reader<'a>,
+    remaining: usize,
+}
+
+impl<'a> BinsRef<'a> {
+    fn read_bin(&mut self) -> Result<(&'a str, ValueRef<'a>)> {
+        let op_size = self.reader.read_u32()? as usize;
+        let op = self.reader.read(op_size)?;
+        if op.len() < 4 || op.len() < 4 + op[3] as usize {
+            bail!(ErrorKind::BadResponse("Invalid bin operation".to_string()));
+        }
+        let particle_type = op[1];
+        let (name, particle) = op[4..].split_at(op[3] as usize);
+        Ok((
+            str::from_utf8(name)?,
+            ValueRef::from_particle(particle_type, particle)?,
+        ))
+    }
+}
+
+impl<'a> Iterator for BinsRef<'a> {
+    type Item = (&'a str, ValueRef<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        // The bins have been validated when the record was parsed.
+        Some(self.read_bin().expect("Invalid bin"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for BinsRef<'a> {}
+
+// Minimal big-endian reader over a byte slice.
+#[derive(Debug, Clone)]
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(buf: &'a [u8]) -> Self {
+        Reader { buf }
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.buf.len() {
+            bail!(ErrorKind::BadResponse(format!(
+                "Unexpected end of record: {} bytes expected, {} available",
+                len,
+                self.buf.len()
+            )));
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        Ok(BigEndian::read_u16(self.read(2)?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(BigEndian::read_u32(self.read(4)?))
+    }
+
+    const fn rest(&self) -> &'a [u8] {
+        self.buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecordRef, ValueRef};
+    use crate::commands::field_type::FieldType;
+    use crate::commands::ParticleType;
+    use crate::Value;
+
+    fn field(ftype: FieldType, data: &[u8]) -> Vec<u8> {
+        let mut buf = ((data.len() + 1) as u32).to_be_bytes().to_vec();
+        buf.push(ftype as u8);
+        buf.extend_from_slice(data);
+        buf
+    }
+
+    fn op(name: &str, ptype: ParticleType, data: &[u8]) -> Vec<u8> {
+        let mut buf = ((4 + name.len() + data.len()) as u32)
+            .to_be_bytes()
+            .to_vec();
+        buf.extend_from_slice(&[1, ptype as u8, 0, name.len() as u8]);
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(data);
+        buf
+    }
+
+    fn record() -> Vec<u8> {
+        let mut buf = vec![22, 0, 0, 0, 0, 0];
+        buf.extend_from_slice(&7u32.to_be_bytes()); // generation
+        buf.extend_from_slice(&0u32.to_be_bytes()); // expiration
+        buf.extend_from_slice(&0u32.to_be_bytes());
+        buf.extend_from_slice(&3u16.to_be_bytes()); // fields
+        buf.extend_from_slice(&4u16.to_be_bytes()); // ops
+        buf.extend(field(FieldType::Namespace, b"test"));
+        buf.extend(field(FieldType::Table, b"demo"));
+        buf.extend(field(FieldType::DigestRipe, &[1; 20]));
+        buf.extend(op("int", ParticleType::INTEGER, &42i64.to_be_bytes()));
+        buf.extend(op("str", ParticleType::STRING, b"hello"));
+        buf.extend(op(
+            "list",
+            ParticleType::LIST,
+            &[0x92, 0x01, 0xa2, 0x03, b'a'],
+        ));
+        buf.extend(op("nil", ParticleType::NULL, &[]));
+        buf
+    }
+
+    #[test]
+    fn parse_record() {
+        let buf = record();
+        let rec = RecordRef::parse(&buf).unwrap();
+        assert_eq!(rec.namespace(), "test");
+        assert_eq!(rec.set_name(), "demo");
+        assert_eq!(rec.digest(), &[1; 20]);
+        assert_eq!(rec.user_key(), None);
+        assert_eq!(rec.generation(), 7);
+        assert_eq!(rec.time_to_live(), None);
+        assert_eq!(rec.bin_count(), 4);
+
+        let names: Vec<&str> = rec.bins().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["int", "str", "list", "nil"]);
+        assert_eq!(rec.get("int"), Some(ValueRef::Int(42)));
+        assert_eq!(rec.get("str").and_then(|v| v.as_str()), Some("hello"));
+        assert_eq!(rec.get("nil"), Some(ValueRef::Nil));
+        assert_eq!(rec.get("missing"), None);
+        assert_eq!(
+            rec.get("list").unwrap().to_value().unwrap(),
+            as_list!(1, "a")
+        );
+
+        let owned = rec.to_record().unwrap();
+        assert_eq!(owned.generation, 7);
+        assert_eq!(owned.bins.len(), 4);
+        assert_eq!(owned.bins["str"], Value::from("hello"));
+        assert_eq!(owned.key.unwrap().namespace, "test");
+    }
+
+    #[test]
+    fn parse_truncated_record() {
+        let buf = record();
+        for len in &[10, 30, buf.len() - 1] {
+            assert!(RecordRef::parse(&buf[..*len]).is_err());
+        }
+    }
+
+    #[test]
+    fn digest_particle_is_blob() {
+        let digest = [7u8; 20];
+        let value = ValueRef::from_particle(ParticleType::DIGEST as u8, &digest).unwrap();
+        assert_eq!(value, ValueRef::Blob(&digest));
+        assert_eq!(value.to_value().unwrap(), Value::Blob(digest.to_vec()));
+    }
+}
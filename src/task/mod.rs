@@ -20,8 +20,10 @@ pub use self::index_task::IndexTask;
 pub use self::register_task::RegisterTask;
 pub use self::task::Status;
 pub use self::task::Task;
+pub use self::truncate_task::TruncateTask;
 
 mod index_task;
 mod register_task;
 #[allow(clippy::module_inception)]
 mod task;
+mod truncate_task;
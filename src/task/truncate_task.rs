@@ -0,0 +1,166 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::cluster::{Cluster, Node};
+use crate::errors::{ErrorKind, Result};
+use crate::info::SetInfo;
+use crate::task::{Status, Task};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Struct for querying the status of a set truncation
+#[derive(Debug, Clone)]
+pub struct TruncateTask {
+    cluster: Arc<Cluster>,
+    namespace: String,
+    set_name: String,
+    // truncate LUT of the set on each node before the truncation, by node name
+    previous_luts: HashMap<String, u64>,
+}
+
+impl TruncateTask {
+    /// Initializes `TruncateTask` from client, creation should only be expose to Client. Records
+    /// the current truncate LUT of the set on every node, so it must be created before the
+    /// truncate command is sent.
+    pub fn new(cluster: Arc<Cluster>, namespace: String, set_name: String) -> Result<Self> {
+        let command = TruncateTask::build_command(&namespace);
+        let mut previous_luts = HashMap::new();
+        for node in &cluster.nodes() {
+            let set = TruncateTask::find_set(&cluster, node, &command, &set_name)?;
+            let lut = set.map_or(0, |set| set.truncate_lut);
+            previous_luts.insert(node.name().to_owned(), lut);
+        }
+
+        Ok(TruncateTask {
+            cluster,
+            namespace,
+            set_name,
+            previous_luts,
+        })
+    }
+
+    fn build_command(namespace: &str) -> String {
+        format!("sets/{}", namespace)
+    }
+
+    fn find_set(
+        cluster: &Cluster,
+        node: &Node,
+        command: &str,
+        set_name: &str,
+    ) -> Result<Option<SetInfo>> {
+        let response = node.info(cluster.client_policy().timeout, &[command])?;
+        Ok(response.get(command).and_then(|response| {
+            SetInfo::parse_list(response)
+                .into_iter()
+                .find(|set| set.set_name == set_name)
+        }))
+    }
+
+    // The truncation is complete on a node once the set's truncate LUT has moved past the one
+    // recorded before the truncation: from then on, the server treats all older records as
+    // deleted, even while it is still reclaiming their space, so that later writes to the set do
+    // not affect the status. A set the node does not know has no records to delete. Servers that
+    // do not report the truncate LUT are checked for remaining objects instead.
+    fn node_status(set: Option<&SetInfo>, previous_lut: u64) -> Status {
+        match set {
+            None => Status::Complete,
+            Some(set) if !set.values.contains_key("truncate_lut") => {
+                if set.objects == 0 {
+                    Status::Complete
+                } else {
+                    Status::InProgress
+                }
+            }
+            Some(set) if set.truncate_lut > previous_lut => Status::Complete,
+            Some(_) => Status::InProgress,
+        }
+    }
+}
+
+impl Task for TruncateTask {
+    /// Query the status of the set truncation across all nodes
+    fn query_status(&self) -> Result<Status> {
+        let nodes = self.cluster.nodes();
+
+        if nodes.is_empty() {
+            bail!(ErrorKind::Connection("No connected node".to_string()))
+        }
+
+        let command = &TruncateTask::build_command(&self.namespace);
+        for node in &nodes {
+            let set = TruncateTask::find_set(&self.cluster, node, command, &self.set_name)?;
+            let previous_lut = self.previous_luts.get(node.name()).cloned().unwrap_or(0);
+            if let Status::InProgress = TruncateTask::node_status(set.as_ref(), previous_lut) {
+                return Ok(Status::InProgress);
+            }
+        }
+        Ok(Status::Complete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TruncateTask;
+    use crate::info::SetInfo;
+    use crate::task::Status;
+
+    fn set(entry: &str) -> SetInfo {
+        SetInfo::parse(entry)
+    }
+
+    #[test]
+    fn node_status() {
+        // set unknown to the node
+        assert!(matches!(
+            TruncateTask::node_status(None, 0),
+            Status::Complete
+        ));
+
+        // truncation registered, even though new records have been written since
+        let truncated = set("ns=test:set=demo:objects=5:truncate_lut=2000");
+        assert!(matches!(
+            TruncateTask::node_status(Some(&truncated), 1000),
+            Status::Complete
+        ));
+        assert!(matches!(
+            TruncateTask::node_status(Some(&truncated), 0),
+            Status::Complete
+        ));
+
+        // truncation not registered yet
+        assert!(matches!(
+            TruncateTask::node_status(Some(&truncated), 2000),
+            Status::InProgress
+        ));
+        let never_truncated = set("ns=test:set=demo:objects=0:truncate_lut=0");
+        assert!(matches!(
+            TruncateTask::node_status(Some(&never_truncated), 0),
+            Status::InProgress
+        ));
+
+        // servers without truncate LUT
+        let remaining = set("ns=test:set=demo:objects=5");
+        assert!(matches!(
+            TruncateTask::node_status(Some(&remaining), 0),
+            Status::InProgress
+        ));
+        let empty = set("ns=test:set=demo:objects=0");
+        assert!(matches!(
+            TruncateTask::node_status(Some(&empty), 0),
+            Status::Complete
+        ));
+    }
+}
@@ -0,0 +1,389 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Spans for the optional integration with the `tracing` crate.
+//!
+//! Every command sent to a node gets a span with the command type, the key's namespace, set and
+//! digest, the node, the number of attempts and the result code. Each attempt of a command opens
+//! a child span. Batch, scan and query operations open a parent span for the whole operation,
+//! which is entered on the worker threads executing the per-node commands. Without the `tracing`
+//! feature all spans are no-ops.
+
+pub use self::imp::Span;
+
+#[cfg(feature = "tracing")]
+mod imp {
+    use std::fmt;
+
+    use tracing::field::Empty;
+    use tracing::{debug_span, info_span};
+
+    use crate::cluster::Node;
+    use crate::errors::{ErrorKind, Result};
+    use crate::metrics::CommandType;
+    use crate::Key;
+
+    #[derive(Debug, Clone)]
+    pub struct Span(tracing::Span);
+
+    impl Span {
+        // Span of a batch, scan or query operation that is fanned out to multiple nodes.
+        pub fn fan_out(command_type: CommandType, namespace: &str, set_name: &str) -> Self {
+            Span(info_span!(
+                "aerospike.fan_out",
+                command = command_type.as_str(),
+                namespace,
+                set = set_name,
+            ))
+        }
+
+        // Span of a command sent to a single node.
+        pub fn command(command_type: CommandType, key: Option<&Key>) -> Self {
+            let span = info_span!(
+                "aerospike.command",
+                command = command_type.as_str(),
+                namespace = Empty,
+                set = Empty,
+                digest = Empty,
+                node = Empty,
+                attempt = Empty,
+                result_code = Empty,
+                error = Empty,
+            );
+            if let Some(key) = key {
+                span.record("namespace", key.namespace.as_str());
+                span.record("set", key.set_name.as_str());
+                span.record("digest", tracing::field::display(Digest(&key.digest)));
+            }
+            Span(span)
+        }
+
+        // Span of a batch read operation, fanned out to the nodes owning the keys.
+        pub fn batch(keys: usize) -> Self {
+            Span(info_span!(
+                "aerospike.fan_out",
+                command = CommandType::Batch.as_str(),
+                keys,
+            ))
+        }
+
+        // Child span of a single attempt of this command. The node and attempt are also recorded
+        // on the command span.
+        pub fn attempt(&self, attempt: usize, node: &Node) -> Self {
+            self.0.record("node", node.name());
+            self.0.record("attempt", attempt);
+            Span(debug_span!(
+                parent: &self.0,
+                "aerospike.attempt",
+                attempt,
+                node = node.name(),
+            ))
+        }
+
+        // Span of a cluster tend cycle.
+        pub fn tend() -> Self {
+            Span(debug_span!("aerospike.tend", nodes = Empty))
+        }
+
+        // Returns the current span, to enter it on the worker threads of a fan-out operation.
+        pub fn current() -> Self {
+            Span(tracing::Span::current())
+        }
+
+        pub fn enter(&self) -> tracing::span::Entered<'_> {
+            self.0.enter()
+        }
+
+        pub fn record_nodes(&self, nodes: usize) {
+            self.0.record("nodes", nodes);
+        }
+
+        // Records the result code of the command, or the error if it did not get a response.
+        pub fn record_result(&self, result: &Result<()>) {
+            match result {
+                Ok(()) => {
+                    self.0.record("result_code", "Ok");
+                }
+                Err(err) => match *err.kind() {
+                    ErrorKind::ServerError(result_code) => {
+                        self.0
+                            .record("result_code", tracing::field::debug(result_code));
+                    }
+                    _ => {
+                        self.0.record("error", tracing::field::display(err));
+                    }
+                },
+            }
+        }
+    }
+
+    struct Digest<'a>(&'a [u8]);
+
+    impl fmt::Display for Digest<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for b in self.0 {
+                write!(f, "{:02x}", b)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::HashMap;
+        use std::fmt;
+        use std::sync::{Arc, Mutex};
+
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        use super::Digest;
+        use crate::batch::batch_executor::SharedSlice;
+        use crate::cluster::Node;
+        use crate::commands::test_util::{self, RefusingTransport, StubCommand};
+        use crate::commands::{BatchReadCommand, SingleCommand};
+        use crate::policy::{BatchPolicy, ReadPolicy};
+        use crate::{BatchRead, Bins};
+
+        #[derive(Debug, Clone)]
+        struct RecordedSpan {
+            name: &'static str,
+            parent: Option<&'static str>,
+            fields: HashMap<&'static str, String>,
+        }
+
+        impl Visit for RecordedSpan {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.fields.insert(field.name(), value.to_owned());
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                self.fields.insert(field.name(), format!("{:?}", value));
+            }
+        }
+
+        // Records the spans created on the current thread with their fields and parents.
+        #[derive(Clone, Default)]
+        struct Recorder {
+            spans: Arc<Mutex<Vec<RecordedSpan>>>,
+            stack: Arc<Mutex<Vec<u64>>>,
+        }
+
+        impl Recorder {
+            fn span(&self, id: &Id) -> RecordedSpan {
+                self.spans.lock().unwrap()[id.into_u64() as usize - 1].clone()
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn new_span(&self, attrs: &Attributes) -> Id {
+                let parent = if attrs.is_root() {
+                    None
+                } else if let Some(parent) = attrs.parent() {
+                    Some(parent.into_u64())
+                } else {
+                    self.stack.lock().unwrap().last().copied()
+                };
+                let mut spans = self.spans.lock().unwrap();
+                let mut span = RecordedSpan {
+                    name: attrs.metadata().name(),
+                    parent: parent.map(|id| spans[id as usize - 1].name),
+                    fields: HashMap::new(),
+                };
+                attrs.record(&mut span);
+                spans.push(span);
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, id: &Id, values: &Record) {
+                let mut spans = self.spans.lock().unwrap();
+                values.record(&mut spans[id.into_u64() as usize - 1]);
+            }
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, _event: &Event) {}
+
+            fn enter(&self, id: &Id) {
+                self.stack.lock().unwrap().push(id.into_u64());
+            }
+
+            fn exit(&self, _id: &Id) {
+                self.stack.lock().unwrap().pop();
+            }
+        }
+
+        fn node() -> Arc<Node> {
+            let transport = Arc::new(RefusingTransport::default());
+            test_util::node(test_util::refusing_policy(transport))
+        }
+
+        fn attempts(spans: &[RecordedSpan]) -> Vec<(String, String)> {
+            spans
+                .iter()
+                .filter(|span| span.name == "aerospike.attempt")
+                .map(|span| {
+                    assert_eq!(span.parent, Some("aerospike.command"));
+                    (span.fields["attempt"].clone(), span.fields["node"].clone())
+                })
+                .collect()
+        }
+
+        #[test]
+        fn digest() {
+            assert_eq!(Digest(&[0x00, 0x1f, 0xab]).to_string(), "001fab");
+        }
+
+        #[test]
+        fn single_key_command_spans() {
+            let recorder = Recorder::default();
+            let key = as_key!("test", "spans", 1);
+            let digest = Digest(&key.digest).to_string();
+            let mut cmd = StubCommand {
+                node: node(),
+                key: Some(key),
+            };
+            let policy = ReadPolicy {
+                max_retries: Some(1),
+                sleep_between_retries: None,
+                ..ReadPolicy::default()
+            };
+
+            let result = tracing::subscriber::with_default(recorder.clone(), || {
+                SingleCommand::execute(&policy, &mut cmd)
+            });
+            assert!(result.is_err());
+
+            let command = recorder.span(&Id::from_u64(1));
+            assert_eq!(command.name, "aerospike.command");
+            assert_eq!(command.parent, None);
+            assert_eq!(command.fields["command"], "read");
+            assert_eq!(command.fields["namespace"], "test");
+            assert_eq!(command.fields["set"], "spans");
+            assert_eq!(command.fields["digest"], digest);
+            assert_eq!(command.fields["node"], "BB9");
+            assert_eq!(command.fields["attempt"], "2");
+            assert!(command.fields.contains_key("error"));
+
+            let attempts = attempts(&recorder.spans.lock().unwrap());
+            assert_eq!(
+                attempts,
+                vec![
+                    ("1".to_owned(), "BB9".to_owned()),
+                    ("2".to_owned(), "BB9".to_owned())
+                ]
+            );
+        }
+
+        #[test]
+        fn batch_command_spans() {
+            let recorder = Recorder::default();
+            let bins = Bins::All;
+            let batch_reads = vec![
+                BatchRead::new(as_key!("test", "spans", 1), &bins),
+                BatchRead::new(as_key!("test", "spans", 2), &bins),
+            ];
+            let mut policy = BatchPolicy::default();
+            policy.base_policy.max_retries = Some(1);
+            policy.base_policy.sleep_between_retries = None;
+
+            let result = tracing::subscriber::with_default(recorder.clone(), || {
+                let span = super::Span::batch(batch_reads.len());
+                let _entered = span.enter();
+                let batch_reads = SharedSlice::new(batch_reads);
+                BatchReadCommand::new(&policy, node(), batch_reads, vec![0, 1]).execute()
+            });
+            assert!(result.is_err());
+
+            let fan_out = recorder.span(&Id::from_u64(1));
+            assert_eq!(fan_out.name, "aerospike.fan_out");
+            assert_eq!(fan_out.fields["command"], "batch");
+            assert_eq!(fan_out.fields["keys"], "2");
+
+            let command = recorder.span(&Id::from_u64(2));
+            assert_eq!(command.name, "aerospike.command");
+            assert_eq!(command.parent, Some("aerospike.fan_out"));
+            assert_eq!(command.fields["command"], "batch");
+            assert!(!command.fields.contains_key("namespace"));
+            assert_eq!(command.fields["node"], "BB9");
+            assert_eq!(command.fields["attempt"], "2");
+
+            let attempts = attempts(&recorder.spans.lock().unwrap());
+            assert_eq!(
+                attempts,
+                vec![
+                    ("1".to_owned(), "BB9".to_owned()),
+                    ("2".to_owned(), "BB9".to_owned())
+                ]
+            );
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod imp {
+    use crate::cluster::Node;
+    use crate::errors::Result;
+    use crate::metrics::CommandType;
+    use crate::Key;
+
+    #[derive(Debug, Clone)]
+    pub struct Span;
+
+    pub struct Entered;
+
+    impl Span {
+        pub const fn fan_out(
+            _command_type: CommandType,
+            _namespace: &str,
+            _set_name: &str,
+        ) -> Self {
+            Span
+        }
+
+        pub const fn command(_command_type: CommandType, _key: Option<&Key>) -> Self {
+            Span
+        }
+
+        pub const fn batch(_keys: usize) -> Self {
+            Span
+        }
+
+        pub const fn attempt(&self, _attempt: usize, _node: &Node) -> Self {
+            Span
+        }
+
+        pub const fn tend() -> Self {
+            Span
+        }
+
+        pub const fn current() -> Self {
+            Span
+        }
+
+        pub const fn enter(&self) -> Entered {
+            Entered
+        }
+
+        pub const fn record_nodes(&self, _nodes: usize) {}
+
+        pub const fn record_result(&self, _result: &Result<()>) {}
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Pluggable transports for the connections to the cluster nodes.
+//!
+//! The client opens its connections through the `Transport` set on the `ClientPolicy`; the
+//! default is `TcpTransport`. A custom transport can route the connections through e.g. a Unix
+//! domain socket of a sidecar proxy, serve them from memory in unit tests, or inject faults in
+//! resilience tests.
+//!
+//! # Examples
+//!
+//! A transport that fails every tenth connection attempt.
+//!
+//! ```rust
+//! # use aerospike::*;
+//! use std::io;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use aerospike::transport::{Stream, TcpTransport, Transport};
+//!
+//! #[derive(Debug, Default)]
+//! struct FlakyTransport {
+//!     attempts: AtomicUsize,
+//! }
+//!
+//! impl Transport for FlakyTransport {
+//!     fn connect(&self, host: &Host, timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+//!         if self.attempts.fetch_add(1, Ordering::Relaxed) % 10 == 9 {
+//!             return Err(io::Error::new(io::ErrorKind::ConnectionRefused, "injected fault"));
+//!         }
+//!         TcpTransport.connect(host, timeout)
+//!     }
+//! }
+//!
+//! let mut policy = ClientPolicy::default();
+//! policy.transport = Arc::new(FlakyTransport::default());
+//! # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+//! let client = Client::new(&policy, &hosts).unwrap();
+//! ```
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::net::Host;
+
+/// Bidirectional byte stream to a cluster node, as opened by a `Transport`.
+///
+/// Read and write timeouts must be reported as errors of kind `WouldBlock` or `TimedOut`, so
+/// that the client can tell them apart from other I/O errors.
+pub trait Stream: fmt::Debug + Send {
+    /// Read exactly enough bytes to fill `buf`.
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()>;
+
+    /// Write the entire content of `buf`.
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()>;
+
+    /// Set the timeout of read operations; `None` blocks indefinitely.
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Set the timeout of write operations; `None` blocks indefinitely.
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Shut down both directions of the stream.
+    fn shutdown(&mut self) -> io::Result<()>;
+}
+
+/// Opens the streams to the cluster nodes. Set through `ClientPolicy::transport`.
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Open a stream to the given host. The `timeout` bounds the time spent connecting.
+    fn connect(&self, host: &Host, timeout: Option<Duration>) -> io::Result<Box<dyn Stream>>;
+}
+
+/// Transport that connects to the nodes over TCP. This is the default transport.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TcpTransport;
+
+impl Transport for TcpTransport {
+    fn connect(&self, host: &Host, timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+        let timeout = match timeout {
+            Some(timeout) if timeout > Duration::from_secs(0) => timeout,
+            _ => return Ok(Box::new(TcpStream::connect(host)?)),
+        };
+
+        // try all addresses the host resolves to
+        let mut last_err = None;
+        for addr in host.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => return Ok(Box::new(stream)),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Host {} did not resolve to any address", host),
+            )
+        }))
+    }
+}
+
+impl Stream for TcpStream {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        Read::read_exact(self, buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        Write::write_all(self, buf)
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_write_timeout(self, timeout)
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        TcpStream::shutdown(self, Shutdown::Both)
+    }
+}
@@ -13,9 +13,11 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::result::Result as StdResult;
 use std::{f32, f64};
 
@@ -28,9 +30,12 @@ use std::vec::Vec;
 
 use crate::commands::buffer::Buffer;
 use crate::commands::ParticleType;
-use crate::errors::Result;
+use crate::errors::{Error, ErrorKind, Result};
+use crate::mapping::FromValue;
 use crate::msgpack::{decoder, encoder};
 
+#[cfg(feature = "serialization")]
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 #[cfg(feature = "serialization")]
 use serde::ser::{SerializeMap, SerializeSeq};
 #[cfg(feature = "serialization")]
@@ -45,6 +50,24 @@ pub enum FloatValue {
     F64(u64),
 }
 
+impl FloatValue {
+    // Returns the value as double precision float, converting single precision values losslessly.
+    pub(crate) fn as_f64(&self) -> f64 {
+        match *self {
+            FloatValue::F32(val) => f64::from(f32::from_bits(val)),
+            FloatValue::F64(val) => f64::from_bits(val),
+        }
+    }
+
+    // Tie breaker for equal values of different precision.
+    const fn precision(&self) -> u8 {
+        match *self {
+            FloatValue::F32(_) => 1,
+            FloatValue::F64(_) => 2,
+        }
+    }
+}
+
 impl From<FloatValue> for f64 {
     fn from(val: FloatValue) -> f64 {
         match val {
@@ -193,6 +216,16 @@ pub enum Value {
 
     /// HLL value
     HLL(Vec<u8>),
+
+    /// Wildcard value, which matches any value in CDT comparisons, e.g. `[user_id, Wildcard]` to
+    /// select all list items or map keys that are lists starting with `user_id`. Can only be used
+    /// in CDT operations and expressions, not as bin value.
+    Wildcard,
+
+    /// Infinity value, which is greater than any other value in CDT comparisons, e.g. as open
+    /// upper bound of value ranges. Can only be used in CDT operations and expressions, not as bin
+    /// value.
+    Infinity,
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
@@ -212,10 +245,111 @@ impl Hash for Value {
             Value::List(ref val) => val.hash(state),
             Value::HashMap(_) => panic!("HashMaps cannot be used as map keys."),
             Value::OrderedMap(_) => panic!("OrderedMaps cannot be used as map keys."),
+            Value::Wildcard | Value::Infinity => self.type_order().hash(state),
+        }
+    }
+}
+
+// Orders floats like `f64::total_cmp`, which is not available on the minimum supported Rust
+// version: the bits of negative values are flipped so that they compare as signed integers.
+fn total_cmp(a: f64, b: f64) -> Ordering {
+    let mut a = a.to_bits() as i64;
+    let mut b = b.to_bits() as i64;
+    a ^= (((a >> 63) as u64) >> 1) as i64;
+    b ^= (((b >> 63) as u64) >> 1) as i64;
+    a.cmp(&b)
+}
+
+// Compares values of different types the same way the server orders CDT values: nil < bool <
+// int < string < list < map < blob < float < geojson. Values of the same type are compared by
+// value; variants that share a type on the server (e.g. `Int` and `UInt`) are ordered by variant
+// only if their values are equal, to keep the ordering consistent with `Eq`. `Infinity` is greater
+// than any other value; `Wildcard`, which matches any value on the server, is ordered right
+// before `Infinity`.
+impl Ord for Value {
+    fn cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Nil, Value::Nil)
+            | (Value::Wildcard, Value::Wildcard)
+            | (Value::Infinity, Value::Infinity) => Ordering::Equal,
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::UInt(a), Value::UInt(b)) => a.cmp(b),
+            (Value::Int(a), Value::UInt(b)) => {
+                i128::from(*a).cmp(&i128::from(*b)).then(Ordering::Less)
+            }
+            (Value::UInt(a), Value::Int(b)) => {
+                i128::from(*a).cmp(&i128::from(*b)).then(Ordering::Greater)
+            }
+            (Value::Float(a), Value::Float(b)) => {
+                // NaN is greater than any other float; equal values of different precision are
+                // ordered single precision first.
+                total_cmp(a.as_f64(), b.as_f64()).then_with(|| a.precision().cmp(&b.precision()))
+            }
+            (Value::String(a), Value::String(b)) | (Value::GeoJSON(a), Value::GeoJSON(b)) => {
+                a.cmp(b)
+            }
+            (Value::Blob(a), Value::Blob(b)) | (Value::HLL(a), Value::HLL(b)) => a.cmp(b),
+            (Value::Blob(a), Value::HLL(b)) => a.cmp(b).then(Ordering::Less),
+            (Value::HLL(a), Value::Blob(b)) => a.cmp(b).then(Ordering::Greater),
+            (Value::List(a), Value::List(b)) => a.cmp(b),
+            (a, b) if a.type_order() == b.type_order() => {
+                // Both values are maps: compare by size first, then entry by entry in key order.
+                let (a, b) = (a.sorted_entries(), b.sorted_entries());
+                a.len()
+                    .cmp(&b.len())
+                    .then_with(|| a.cmp(&b))
+                    .then_with(|| self.variant_order().cmp(&other.variant_order()))
+            }
+            (a, b) => a.type_order().cmp(&b.type_order()),
         }
     }
 }
 
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Value {
+    // Rank of the value's type in the server's cross-type CDT ordering.
+    const fn type_order(&self) -> u8 {
+        match *self {
+            Value::Nil => 1,
+            Value::Bool(_) => 2,
+            Value::Int(_) | Value::UInt(_) => 3,
+            Value::String(_) => 4,
+            Value::List(_) => 5,
+            Value::HashMap(_) | Value::OrderedMap(_) => 6,
+            Value::Blob(_) | Value::HLL(_) => 7,
+            Value::Float(_) => 8,
+            Value::GeoJSON(_) => 9,
+            Value::Wildcard => 10,
+            Value::Infinity => 11,
+        }
+    }
+
+    // Tie breaker for the different map variants.
+    const fn variant_order(&self) -> u8 {
+        match *self {
+            Value::OrderedMap(_) => 1,
+            _ => 0,
+        }
+    }
+
+    // Map entries sorted by key.
+    fn sorted_entries(&self) -> Vec<(&Value, &Value)> {
+        let mut entries: Vec<(&Value, &Value)> = match *self {
+            Value::HashMap(ref map) => map.iter().collect(),
+            Value::OrderedMap(ref map) => map.iter().map(|(k, v)| (k, v)).collect(),
+            _ => vec![],
+        };
+        entries.sort();
+        entries
+    }
+}
+
 impl Value {
     /// Returns true if this value is the empty value (nil).
     pub const fn is_nil(&self) -> bool {
@@ -241,6 +375,8 @@ impl Value {
             Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
             Value::GeoJSON(_) => ParticleType::GEOJSON,
             Value::HLL(_) => ParticleType::HLL,
+            // rejected by `estimate_size` and `write_to`; only valid in CDT operations
+            Value::Wildcard | Value::Infinity => ParticleType::NULL,
         }
     }
 
@@ -257,6 +393,8 @@ impl Value {
             Value::List(ref val) => format!("{:?}", val),
             Value::HashMap(ref val) => format!("{:?}", val),
             Value::OrderedMap(ref val) => format!("{:?}", val),
+            Value::Wildcard => "<wildcard>".to_string(),
+            Value::Infinity => "<infinity>".to_string(),
         }
     }
 
@@ -277,6 +415,9 @@ impl Value {
             Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
             Value::GeoJSON(ref s) => Ok(1 + 2 + s.len()), // flags + ncells + jsonstr
             Value::HLL(ref h) => Ok(h.len()),
+            Value::Wildcard | Value::Infinity => bail!(ErrorKind::InvalidArgument(
+                "Wildcard and infinity values can only be used in CDT operations".to_string()
+            )),
         }
     }
 
@@ -298,6 +439,9 @@ impl Value {
             Value::List(_) | Value::HashMap(_) => encoder::pack_value(&mut Some(buf), self),
             Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
             Value::GeoJSON(ref val) => buf.write_geo(val),
+            Value::Wildcard | Value::Infinity => bail!(ErrorKind::InvalidArgument(
+                "Wildcard and infinity values can only be used in CDT operations".to_string()
+            )),
         }
     }
 
@@ -535,30 +679,104 @@ impl<'a> From<&'a bool> for Value {
     }
 }
 
-impl From<Value> for i64 {
-    fn from(val: Value) -> i64 {
-        match val {
-            Value::Int(val) => val,
-            Value::UInt(val) => val as i64,
-            _ => panic!("Value is not an integer to convert."),
-        }
+macro_rules! try_from_value {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<Value> for $t {
+                type Error = Error;
+
+                fn try_from(val: Value) -> Result<$t> {
+                    FromValue::from_value(&val)
+                }
+            }
+
+            impl<'a> TryFrom<&'a Value> for $t {
+                type Error = Error;
+
+                fn try_from(val: &'a Value) -> Result<$t> {
+                    FromValue::from_value(val)
+                }
+            }
+        )*
+    };
+}
+
+try_from_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, bool, String);
+
+impl<T: FromValue> TryFrom<Value> for Vec<T> {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<Vec<T>> {
+        FromValue::from_value(&val)
     }
 }
 
-impl<'a> From<&'a Value> for i64 {
-    fn from(val: &'a Value) -> i64 {
-        match *val {
-            Value::Int(val) => val,
-            Value::UInt(val) => val as i64,
-            _ => panic!("Value is not an integer to convert."),
-        }
+impl<'a, T: FromValue> TryFrom<&'a Value> for Vec<T> {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<Vec<T>> {
+        FromValue::from_value(val)
+    }
+}
+
+impl<K, V, S> TryFrom<Value> for HashMap<K, V, S>
+where
+    K: FromValue + Eq + Hash,
+    V: FromValue,
+    S: BuildHasher + Default,
+{
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<HashMap<K, V, S>> {
+        FromValue::from_value(&val)
+    }
+}
+
+impl<'a, K, V, S> TryFrom<&'a Value> for HashMap<K, V, S>
+where
+    K: FromValue + Eq + Hash,
+    V: FromValue,
+    S: BuildHasher + Default,
+{
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<HashMap<K, V, S>> {
+        FromValue::from_value(val)
     }
 }
 
+macro_rules! try_from_value_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: FromValue),+> TryFrom<Value> for ($($t,)+) {
+            type Error = Error;
+
+            fn try_from(val: Value) -> Result<($($t,)+)> {
+                FromValue::from_value(&val)
+            }
+        }
+
+        impl<'a, $($t: FromValue),+> TryFrom<&'a Value> for ($($t,)+) {
+            type Error = Error;
+
+            fn try_from(val: &'a Value) -> Result<($($t,)+)> {
+                FromValue::from_value(val)
+            }
+        }
+    };
+}
+
+try_from_value_tuple!(A);
+try_from_value_tuple!(A, B);
+try_from_value_tuple!(A, B, C);
+try_from_value_tuple!(A, B, C, D);
+try_from_value_tuple!(A, B, C, D, E);
+try_from_value_tuple!(A, B, C, D, E, F);
+
 #[doc(hidden)]
 pub fn bytes_to_particle(ptype: u8, buf: &mut Buffer, len: usize) -> Result<Value> {
-    match ParticleType::from(ptype) {
+    match ParticleType::try_from(ptype)? {
         ParticleType::NULL => Ok(Value::Nil),
+        ParticleType::BOOL => Ok(Value::Bool(buf.read_u8(None)? != 0)),
         ParticleType::INTEGER => {
             let val = buf.read_i64(None)?;
             Ok(Value::Int(val))
@@ -580,7 +798,9 @@ pub fn bytes_to_particle(ptype: u8, buf: &mut Buffer, len: usize) -> Result<Valu
             let val = buf.read_str(len - header_size - 3)?;
             Ok(Value::GeoJSON(val))
         }
-        ParticleType::BLOB => Ok(Value::Blob(buf.read_blob(len)?)),
+        ParticleType::BLOB | ParticleType::DIGEST | ParticleType::LDT => {
+            Ok(Value::Blob(buf.read_blob(len)?))
+        }
         ParticleType::LIST => {
             let val = decoder::unpack_value_list(buf)?;
             Ok(val)
@@ -589,8 +809,6 @@ pub fn bytes_to_particle(ptype: u8, buf: &mut Buffer, len: usize) -> Result<Valu
             let val = decoder::unpack_value_map(buf)?;
             Ok(val)
         }
-        ParticleType::DIGEST => Ok(Value::from("A DIGEST, NOT IMPLEMENTED YET!")),
-        ParticleType::LDT => Ok(Value::from("A LDT, NOT IMPLEMENTED YET!")),
         ParticleType::HLL => Ok(Value::HLL(buf.read_blob(len)?)),
     }
 }
@@ -713,6 +931,29 @@ macro_rules! as_map {
     };
 }
 
+// Value types that have no native representation in the serde data model are serialized as
+// single-entry maps, using one of the following keys to tag the type of the value. This allows
+// these values to be deserialized again without loss of type information. To keep the tags
+// unambiguous, string keys of user maps that start with `$` are escaped with another `$`.
+#[cfg(feature = "serialization")]
+const BLOB_TAG: &str = "$blob";
+#[cfg(feature = "serialization")]
+const GEOJSON_TAG: &str = "$geojson";
+#[cfg(feature = "serialization")]
+const HLL_TAG: &str = "$hll";
+#[cfg(feature = "serialization")]
+const ORDERED_MAP_TAG: &str = "$ordered_map";
+#[cfg(feature = "serialization")]
+const WILDCARD_TAG: &str = "$wildcard";
+#[cfg(feature = "serialization")]
+const INFINITY_TAG: &str = "$infinity";
+
+/// Serializes the value using the serde data model. Blobs, GeoJSON, HLL, ordered maps, wildcard
+/// and infinity values are serialized as single-entry maps tagged with the type of the value.
+///
+/// Not all values round-trip through formats like JSON: non-string map keys are converted to
+/// strings by the format and deserialized as strings, and 32-bit floats are deserialized as 64-bit
+/// floats.
 #[cfg(feature = "serialization")]
 impl Serialize for Value {
     fn serialize<S>(
@@ -728,11 +969,10 @@ impl Serialize for Value {
             Value::Int(i) => serializer.serialize_i64(*i),
             Value::UInt(u) => serializer.serialize_u64(*u),
             Value::Float(f) => match f {
-                FloatValue::F32(u) => serializer.serialize_u32(*u),
-                FloatValue::F64(u) => serializer.serialize_u64(*u),
+                FloatValue::F32(u) => serializer.serialize_f32(f32::from_bits(*u)),
+                FloatValue::F64(u) => serializer.serialize_f64(f64::from_bits(*u)),
             },
-            Value::String(s) | Value::GeoJSON(s) => serializer.serialize_str(s),
-            Value::Blob(b) => serializer.serialize_bytes(&b[..]),
+            Value::String(s) => serializer.serialize_str(s),
             Value::List(l) => {
                 let mut seq = serializer.serialize_seq(Some(l.len()))?;
                 for elem in l {
@@ -743,25 +983,275 @@ impl Serialize for Value {
             Value::HashMap(m) => {
                 let mut map = serializer.serialize_map(Some(m.len()))?;
                 for (key, value) in m {
-                    map.serialize_entry(&key, &value)?;
+                    map.serialize_entry(&MapKey(key), &value)?;
                 }
                 map.end()
             }
             Value::OrderedMap(m) => {
-                let mut map = serializer.serialize_map(Some(m.len()))?;
-                for (key, value) in m {
-                    map.serialize_entry(&key, &value)?;
-                }
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(ORDERED_MAP_TAG, m)?;
+                map.end()
+            }
+            Value::GeoJSON(s) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(GEOJSON_TAG, s)?;
                 map.end()
             }
-            Value::HLL(b) => serializer.serialize_bytes(&b[..]),
+            Value::Blob(b) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(BLOB_TAG, &Bytes(b))?;
+                map.end()
+            }
+            Value::HLL(b) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(HLL_TAG, &Bytes(b))?;
+                map.end()
+            }
+            Value::Wildcard => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(WILDCARD_TAG, &())?;
+                map.end()
+            }
+            Value::Infinity => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(INFINITY_TAG, &())?;
+                map.end()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+#[cfg(feature = "serialization")]
+struct ValueVisitor;
+
+#[cfg(feature = "serialization")]
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an Aerospike value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> StdResult<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> StdResult<Value, E> {
+        Ok(Value::Int(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> StdResult<Value, E> {
+        if v > i64::max_value() as u64 {
+            Ok(Value::UInt(v))
+        } else {
+            Ok(Value::Int(v as i64))
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> StdResult<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> StdResult<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> StdResult<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> StdResult<Value, E> {
+        Ok(Value::Blob(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> StdResult<Value, E> {
+        Ok(Value::Blob(v))
+    }
+
+    fn visit_none<E>(self) -> StdResult<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_unit<E>(self) -> StdResult<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> StdResult<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> StdResult<Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut list = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(elem) = seq.next_element()? {
+            list.push(elem);
+        }
+        Ok(Value::List(list))
+    }
+
+    fn visit_map<A>(self, mut access: A) -> StdResult<Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let first: Value = match access.next_key()? {
+            Some(key) => key,
+            None => return Ok(Value::HashMap(HashMap::new())),
+        };
+
+        if let Value::String(ref tag) = first {
+            let value = match tag.as_str() {
+                BLOB_TAG => Some(Value::Blob(access.next_value::<ByteBuf>()?.0)),
+                GEOJSON_TAG => Some(Value::GeoJSON(access.next_value()?)),
+                HLL_TAG => Some(Value::HLL(access.next_value::<ByteBuf>()?.0)),
+                ORDERED_MAP_TAG => Some(Value::OrderedMap(access.next_value()?)),
+                WILDCARD_TAG => {
+                    access.next_value::<()>()?;
+                    Some(Value::Wildcard)
+                }
+                INFINITY_TAG => {
+                    access.next_value::<()>()?;
+                    Some(Value::Infinity)
+                }
+                _ => None,
+            };
+            if let Some(value) = value {
+                if access.next_key::<Value>()?.is_some() {
+                    return Err(de::Error::custom(format!(
+                        "unexpected map entry after \"{}\" value",
+                        tag
+                    )));
+                }
+                return Ok(value);
+            }
+        }
+
+        let mut map = HashMap::with_capacity(access.size_hint().unwrap_or(0) + 1);
+        map.insert(unescape_key(first), access.next_value()?);
+        while let Some((key, value)) = access.next_entry()? {
+            map.insert(unescape_key(key), value);
+        }
+        Ok(Value::HashMap(map))
+    }
+}
+
+// Serializes the key of a user map, escaping string keys that start with `$`, so that they
+// cannot be mistaken for a type tag.
+#[cfg(feature = "serialization")]
+struct MapKey<'a>(&'a Value);
+
+#[cfg(feature = "serialization")]
+impl<'a> Serialize for MapKey<'a> {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::String(s) if s.starts_with('$') => serializer.serialize_str(&format!("${}", s)),
+            key => key.serialize(serializer),
+        }
+    }
+}
+
+// Reverses the escaping of `MapKey`.
+#[cfg(feature = "serialization")]
+fn unescape_key(key: Value) -> Value {
+    match key {
+        Value::String(s) if s.starts_with("$$") => Value::String(s[1..].to_string()),
+        key => key,
+    }
+}
+
+// Serializes a byte array as Base64 encoded string for human readable formats like JSON, and as
+// raw bytes otherwise.
+#[cfg(feature = "serialization")]
+struct Bytes<'a>(&'a [u8]);
+
+#[cfg(feature = "serialization")]
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64::encode(self.0))
+        } else {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+// Deserializes a byte array serialized by `Bytes`.
+#[cfg(feature = "serialization")]
+struct ByteBuf(Vec<u8>);
+
+#[cfg(feature = "serialization")]
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ByteBufVisitor;
+
+        impl<'de> Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a Base64 encoded string or a byte array")
+            }
+
+            fn visit_str<E>(self, v: &str) -> StdResult<ByteBuf, E>
+            where
+                E: de::Error,
+            {
+                base64::decode(v).map(ByteBuf).map_err(E::custom)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> StdResult<ByteBuf, E> {
+                Ok(ByteBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> StdResult<ByteBuf, E> {
+                Ok(ByteBuf(v))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> StdResult<ByteBuf, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(ByteBuf(bytes))
+            }
         }
+
+        deserializer.deserialize_any(ByteBufVisitor)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Value;
+    use crate::commands::buffer::Buffer;
+    use crate::errors::ErrorKind;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
 
     #[test]
     fn as_string() {
@@ -779,6 +1269,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn wildcard_bin_value() {
+        for value in &[Value::Wildcard, Value::Infinity] {
+            let err = value.estimate_size().unwrap_err();
+            assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_)));
+            let mut buf = Buffer::new(1024);
+            assert!(value.write_to(&mut buf).is_err());
+        }
+    }
+
     #[test]
     fn as_geo() {
         let string = String::from(r#"{"type":"Point"}"#);
@@ -786,6 +1286,102 @@ mod tests {
         assert_eq!(as_geo!(string), as_geo!(str));
     }
 
+    #[test]
+    fn ordering() {
+        let mut values = vec![
+            as_geo!(r#"{"type":"Point"}"#),
+            Value::from(1.5),
+            as_blob!(vec![1u8]),
+            as_map!("a" => 1),
+            as_list!(1, 2),
+            as_list!(1),
+            Value::from("b"),
+            Value::from("a"),
+            Value::Int(2),
+            Value::UInt(1),
+            Value::Int(-1),
+            Value::Bool(true),
+            Value::Bool(false),
+            Value::Nil,
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                Value::Nil,
+                Value::Bool(false),
+                Value::Bool(true),
+                Value::Int(-1),
+                Value::UInt(1),
+                Value::Int(2),
+                Value::from("a"),
+                Value::from("b"),
+                as_list!(1),
+                as_list!(1, 2),
+                as_map!("a" => 1),
+                as_blob!(vec![1u8]),
+                Value::from(1.5),
+                as_geo!(r#"{"type":"Point"}"#),
+            ]
+        );
+
+        assert!(as_list!(1, Value::Wildcard) > as_list!(1, "z"));
+        assert!(Value::Infinity > Value::Wildcard);
+        assert!(Value::Infinity > as_geo!(r#"{"type":"Point"}"#));
+
+        assert!(Value::from(-2.5) < Value::from(1.5));
+        assert!(Value::from(-1.0) < Value::from(2.0));
+        assert!(Value::from(-0.0) < Value::from(0.0));
+        assert!(Value::from(f64::NEG_INFINITY) < Value::from(-1.0));
+        assert!(Value::from(-2.0f32) < Value::from(-1.0));
+        assert!(Value::from(1.5f32) < Value::from(2.0));
+        assert!(Value::from(2.5f32) > Value::from(-3.0));
+        assert!(Value::from(1.5f32) < Value::from(1.5));
+        assert!(Value::from(1.5) > Value::from(1.5f32));
+        assert!(Value::from(f32::NAN) > Value::from(f64::INFINITY));
+        assert!(Value::from(f64::NAN) > Value::from(f64::INFINITY));
+        assert_eq!(
+            Value::from(f64::NAN).cmp(&Value::from(f64::NAN)),
+            std::cmp::Ordering::Equal
+        );
+
+        assert!(as_list!(1, "a") < as_list!(1, "b"));
+        assert!(as_list!(2) > as_list!(1, 2));
+        assert!(as_map!("b" => 1) < as_map!("a" => 1, "b" => 1));
+        assert!(as_map!("a" => 1) < as_map!("a" => 2));
+        assert_eq!(
+            as_map!("a" => 1, "b" => 2).cmp(&as_map!("b" => 2, "a" => 1)),
+            std::cmp::Ordering::Equal
+        );
+        assert!(Value::Int(1) < Value::UInt(1));
+        assert!(Value::from(1.5f32) < Value::from(2.5f64));
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(i64::try_from(Value::Int(42)).unwrap(), 42);
+        assert_eq!(u8::try_from(&Value::Int(42)).unwrap(), 42);
+        assert!(u8::try_from(&Value::Int(-1)).is_err());
+        assert_eq!(f64::try_from(Value::from(1.5)).unwrap(), 1.5);
+        assert!(bool::try_from(Value::Bool(true)).unwrap());
+        assert_eq!(String::try_from(&Value::from("a")).unwrap(), "a");
+        assert_eq!(
+            Vec::<u8>::try_from(as_blob!(vec![1u8, 2])).unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(Vec::<i64>::try_from(as_list!(1, 2)).unwrap(), vec![1, 2]);
+        assert_eq!(
+            <(String, i64)>::try_from(&as_list!("a", 1)).unwrap(),
+            ("a".to_string(), 1)
+        );
+
+        let map: HashMap<String, i64> = HashMap::try_from(as_map!("a" => 1)).unwrap();
+        assert_eq!(map.get("a"), Some(&1));
+
+        let err = i64::try_from(Value::from("a")).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid value: expected i64, found string");
+    }
+
     #[test]
     #[cfg(feature = "serialization")]
     fn serializer() {
@@ -793,7 +1389,7 @@ mod tests {
         let json = serde_json::to_string(&val);
         assert_eq!(
             json.unwrap(),
-            "[\"0\",9,8,7,1,4611911198408756429,-1,[5,6,7,8,\"asd\"]]",
+            "[\"0\",9,8,7,1,2.1,-1,[5,6,7,8,\"asd\"]]",
             "List Serialization failed"
         );
 
@@ -802,5 +1398,67 @@ mod tests {
         let json = serde_json::to_string(&val);
         // We only check for the len of the String because HashMap serialization does not keep the key order. Comparing like the list above is not possible.
         assert_eq!(json.unwrap().len(), 48, "Map Serialization failed");
+
+        let json = serde_json::to_string(&Value::from(-0.125f32)).unwrap();
+        assert_eq!(json, "-0.125", "Float Serialization failed");
+
+        let json = serde_json::to_string(&as_map!("$blob" => 1)).unwrap();
+        assert_eq!(json, r#"{"$$blob":1}"#, "Map key escaping failed");
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn deserializer() {
+        let values = vec![
+            Value::Nil,
+            Value::Bool(true),
+            Value::Int(-42),
+            Value::UInt(u64::max_value()),
+            Value::from("asd"),
+            Value::Blob(vec![1, 2, 3, 255]),
+            as_geo!(r#"{"type":"Point","coordinates":[-122.0,37.5]}"#),
+            Value::HLL(vec![0, 4, 8, 0, 0, 1]),
+            as_list!(1, "a", as_list!(2, "b"), Value::Blob(vec![0])),
+            as_map!("a" => 1, "b" => as_list!(1, 2), "c" => as_map!("d" => "e")),
+            Value::OrderedMap(vec![
+                (Value::Int(3), Value::from("c")),
+                (Value::Int(1), Value::from("a")),
+                (Value::from("x"), as_geo!(r#"{"type":"Point"}"#)),
+            ]),
+            as_list!(1, Value::Wildcard, Value::Infinity),
+            Value::from(2.5),
+            as_list!(1.5, as_map!("f" => 1e100)),
+            // user keys that look like type tags
+            as_map!("$blob" => "AQID", "$$x" => 1, "y$" => 2),
+            as_map!("$ordered_map" => as_list!()),
+        ];
+
+        for value in values {
+            let json = serde_json::to_string(&value).unwrap();
+            let restored: Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(value, restored, "Round trip via {} failed", json);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn lossy_round_trip() {
+        // JSON object keys are strings, so integer keys come back as strings
+        let json = serde_json::to_string(&as_map!(1 => "a")).unwrap();
+        let restored: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, as_map!("1" => "a"));
+
+        // 32-bit floats come back as 64-bit floats
+        let json = serde_json::to_string(&Value::from(2.5f32)).unwrap();
+        let restored: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, Value::from(2.5f64));
+        assert_ne!(restored, Value::from(2.5f32));
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn deserialize_json() {
+        let value: Value = serde_json::from_str(r#"{"a": [1, 2.5, null, true]}"#).unwrap();
+        assert_eq!(value, as_map!("a" => as_list!(1, 2.5, Value::Nil, true)));
     }
 }
@@ -449,3 +449,78 @@ fn cdt_list() {
     let rec = client.operate(&wpolicy, &key, ops).unwrap();
     assert_eq!(*rec.bins.get("bin").unwrap(), as_list!(8, 9));
 }
+
+#[test]
+fn cdt_list_value_order() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let val = as_list!(
+        2.5f64,
+        "b",
+        as_list!(1, 2),
+        3,
+        Value::Nil,
+        true,
+        as_list!(1),
+        "a",
+        -1
+    );
+    let wbin = as_bin!("bin", val.clone());
+    client.put(&wpolicy, &key, &vec![&wbin]).unwrap();
+
+    let ops = &vec![lists::get_by_rank_range("bin", 0, ListReturnType::Values)];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+
+    let mut expected = match val {
+        Value::List(values) => values,
+        _ => unreachable!(),
+    };
+    expected.sort();
+    assert_eq!(*rec.bins.get("bin").unwrap(), Value::List(expected));
+}
+
+#[test]
+fn cdt_list_wildcard_infinity() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let val = as_list!(as_list!(1, "a"), as_list!(1, "b"), as_list!(2, "c"), 5, 10);
+    let wbin = as_bin!("bin", val);
+    client.put(&wpolicy, &key, &vec![&wbin]).unwrap();
+
+    let wildcard = as_list!(1, Value::Wildcard);
+    let ops = &vec![lists::get_by_value(
+        "bin",
+        &wildcard,
+        ListReturnType::Values,
+    )];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(as_list!(1, "a"), as_list!(1, "b"))
+    );
+
+    let begin = as_val!(7);
+    let ops = &vec![lists::get_by_value_range(
+        "bin",
+        &begin,
+        &Value::Infinity,
+        ListReturnType::Values,
+    )];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(as_list!(1, "a"), as_list!(1, "b"), as_list!(2, "c"), 10)
+    );
+}
@@ -0,0 +1,140 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use aerospike::*;
+use env_logger;
+
+use crate::common;
+
+#[derive(AerospikeRecord, Debug, Default, PartialEq)]
+struct Address {
+    street: String,
+    #[aerospike(rename = "zip_code")]
+    zip: Option<String>,
+}
+
+#[derive(AerospikeRecord, Debug, PartialEq)]
+struct User {
+    name: String,
+    age: u8,
+    #[aerospike(rename = "mail")]
+    email: Option<String>,
+    tags: Vec<String>,
+    scores: HashMap<String, i64>,
+    address: Address,
+    #[aerospike(skip)]
+    cached: bool,
+}
+
+#[derive(AerospikeRecord, Debug, PartialEq)]
+struct Point {
+    x: f32,
+    y: f64,
+}
+
+fn user() -> User {
+    let mut scores = HashMap::new();
+    scores.insert("math".to_string(), 42);
+    User {
+        name: "Jane".to_string(),
+        age: 42,
+        email: None,
+        tags: vec!["admin".to_string(), "ops".to_string()],
+        scores,
+        address: Address {
+            street: "Main St".to_string(),
+            zip: Some("12345".to_string()),
+        },
+        cached: true,
+    }
+}
+
+#[test]
+fn derive_to_bins() {
+    let bins = user().to_bins();
+    let names: Vec<&str> = bins.iter().map(|b| b.name).collect();
+    assert_eq!(
+        names,
+        vec!["name", "age", "mail", "tags", "scores", "address"]
+    );
+    assert_eq!(bins[2].value, Value::Nil);
+    assert_eq!(
+        bins[5].value,
+        as_map!("street" => "Main St", "zip_code" => "12345")
+    );
+    assert_eq!(
+        User::bin_names(),
+        vec!["name", "age", "mail", "tags", "scores", "address"]
+    );
+}
+
+#[test]
+fn derive_from_record() {
+    let mut bins = HashMap::new();
+    bins.insert("name".to_string(), as_val!("Jane"));
+    bins.insert("age".to_string(), as_val!(300));
+    let record = Record::new(None, bins, 1, 0);
+    let err = User::from_record(&record).unwrap_err();
+    assert_eq!(err.to_string(), "Error reading bin 'age'");
+}
+
+#[test]
+fn derive_floats() {
+    let point = Point { x: 1.5, y: -0.25 };
+    let bins: HashMap<String, Value> = point
+        .to_bins()
+        .into_iter()
+        .map(|bin| (bin.name.to_string(), bin.value))
+        .collect();
+    let record = Record::new(None, bins, 1, 0);
+    assert_eq!(Point::from_record(&record).unwrap(), point);
+
+    // the server stores all floats as 64-bit floats
+    let mut bins = HashMap::new();
+    bins.insert("x".to_string(), as_val!(1.5f64));
+    bins.insert("y".to_string(), as_val!(-0.25f64));
+    let record = Record::new(None, bins, 1, 0);
+    assert_eq!(Point::from_record(&record).unwrap(), point);
+}
+
+#[test]
+fn derive_put_get() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, "derive");
+
+    let user = user();
+    client
+        .put_from(&WritePolicy::default(), &key, &user)
+        .unwrap();
+
+    let record = client.get(&ReadPolicy::default(), &key, Bins::All).unwrap();
+    assert!(!record.bins.contains_key("mail"));
+    assert!(!record.bins.contains_key("cached"));
+
+    let restored: User = client.get_as(&ReadPolicy::default(), &key).unwrap();
+    assert_eq!(
+        restored,
+        User {
+            cached: false,
+            ..user
+        }
+    );
+}
@@ -0,0 +1,76 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::common;
+use env_logger;
+
+use aerospike::operations::document::DocumentOps;
+use aerospike::{as_bin, as_key, as_list, as_map, as_val, Bins, ReadPolicy, WritePolicy};
+
+#[test]
+fn document_operations() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let rpolicy = ReadPolicy::default();
+
+    let key = common::rand_str(10);
+    let key = as_key!(namespace, set_name, &key);
+
+    let doc = as_map!(
+        "profile" => as_map!(
+            "name" => "Jane",
+            "addresses" => as_list!(as_map!("city" => "Munich", "zip" => "80331"))
+        ),
+        "tags" => as_list!("a"),
+        "visits" => 1
+    );
+    let bin = as_bin!("doc", doc);
+    client.put(&wpolicy, &key, &[&bin]).unwrap();
+
+    let mut ops = DocumentOps::new("doc");
+    ops.get("profile.addresses[0].city").unwrap();
+    let rec = client.operate(&wpolicy, &key, &ops.operations()).unwrap();
+    assert_eq!(*rec.bins.get("doc").unwrap(), as_val!("Munich"));
+
+    let mut ops = DocumentOps::new("doc");
+    ops.put("$.profile.addresses[0].city", "Berlin")
+        .unwrap()
+        .delete("profile.addresses[0].zip")
+        .unwrap()
+        .append("tags", "b")
+        .unwrap()
+        .increment("visits", 2)
+        .unwrap()
+        .delete("profile.name")
+        .unwrap();
+    client.operate(&wpolicy, &key, &ops.operations()).unwrap();
+
+    let rec = client.get(&rpolicy, &key, Bins::All).unwrap();
+    assert_eq!(
+        *rec.bins.get("doc").unwrap(),
+        as_map!(
+            "profile" => as_map!("addresses" => as_list!(as_map!("city" => "Berlin"))),
+            "tags" => as_list!("a", "b"),
+            "visits" => 3
+        )
+    );
+
+    client.delete(&wpolicy, &key).unwrap();
+}
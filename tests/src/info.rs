@@ -0,0 +1,151 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use aerospike::*;
+
+use crate::common;
+use env_logger;
+
+#[test]
+fn info() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    for node in client.nodes() {
+        let response = client.info(&node, &["node", "build"]).unwrap();
+        assert_eq!(response.get("node"), Some(&node.name().to_string()));
+        assert!(response.contains_key("build"));
+    }
+}
+
+#[test]
+fn namespace_info() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    for node in client.nodes() {
+        let info = client.namespace_info(&node, namespace).unwrap();
+        assert_eq!(info.name, namespace);
+        assert!(info.replication_factor > 0);
+
+        let config = client.namespace_config(&node, namespace).unwrap();
+        assert!(config.values.contains_key("replication-factor"));
+    }
+}
+
+#[test]
+fn set_and_bin_info() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bin_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, 1);
+    let bin = as_bin!(bin_name, 1);
+    client.put(&WritePolicy::default(), &key, &[&bin]).unwrap();
+
+    let mut objects = 0;
+    for node in client.nodes() {
+        let sets = client.set_info(&node, namespace).unwrap();
+        objects += sets
+            .iter()
+            .filter(|set| &set.set_name == set_name)
+            .map(|set| set.objects)
+            .sum::<u64>();
+
+        let bins = client.bin_info(&node, namespace).unwrap();
+        assert_eq!(bins.namespace, namespace);
+    }
+    assert!(objects >= 1);
+}
+
+#[test]
+fn node_info() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    for node in client.nodes() {
+        let build = client.build_info(&node).unwrap();
+        assert!(build.major > 0);
+
+        let stats = client.node_statistics(&node).unwrap();
+        assert!(stats.cluster_size > 0);
+
+        let cluster_key = client.cluster_stable(&node).unwrap();
+        assert!(!cluster_key.is_empty());
+    }
+}
+
+#[test]
+fn sets() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+    for i in 0..5 {
+        let key = as_key!(namespace, set_name, i);
+        let bin = as_bin!("bin", i);
+        client.put(&wpolicy, &key, &[&bin]).unwrap();
+    }
+
+    let sets = client.sets(namespace).unwrap();
+    let set = sets.iter().find(|set| &set.set_name == set_name).unwrap();
+    assert_eq!(set.namespace, namespace);
+    assert_eq!(set.objects, 5);
+
+    let mut config = info::SetConfig::new();
+    config.stop_writes_count = Some(1000);
+    client
+        .configure_set(&wpolicy, namespace, set_name, &config)
+        .unwrap();
+    for node in client.nodes() {
+        let sets = client.set_info(&node, namespace).unwrap();
+        let set = sets.iter().find(|set| &set.set_name == set_name).unwrap();
+        assert_eq!(set.stop_writes_count, 1000);
+    }
+}
+
+#[test]
+fn set_config() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let nodes = client.nodes();
+    let config = client.namespace_config(&nodes[0], namespace).unwrap();
+
+    // Re-apply the current value so the test does not alter the server configuration.
+    let change = info::ConfigChange::namespace(namespace)
+        .set("nsup-period", config.nsup_period)
+        .rollback_on_failure(true);
+    let results = client.set_config(&WritePolicy::default(), &change).unwrap();
+    assert_eq!(results.len(), nodes.len());
+    for res in results {
+        assert!(res.result.is_ok());
+        assert!(!res.rolled_back);
+        assert_eq!(
+            res.previous.get("nsup-period"),
+            Some(&config.nsup_period.to_string())
+        );
+    }
+
+    let change = info::ConfigChange::namespace(namespace).set("no-such-param", 1);
+    let results = client.set_config(&WritePolicy::default(), &change).unwrap();
+    assert!(results.iter().all(|res| res.result.is_err()));
+}
@@ -0,0 +1,133 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use aerospike::expressions::{eq, int_bin, int_val};
+use aerospike::interceptor::{CommandContext, Interceptor};
+use aerospike::metrics::CommandType;
+use aerospike::{
+    as_bin, as_key, BatchPolicy, BatchRead, Bins, Client, ErrorKind, QueryPolicy, ReadPolicy,
+    Recordset, Result, ScanPolicy, Statement, WritePolicy,
+};
+use env_logger;
+
+use crate::common;
+
+#[derive(Debug, Default)]
+struct TenantInterceptor {
+    before: AtomicUsize,
+    after: AtomicUsize,
+    keys: AtomicUsize,
+}
+
+impl Interceptor for TenantInterceptor {
+    fn before_command(&self, ctx: &mut CommandContext) -> Result<()> {
+        self.before.fetch_add(1, Ordering::SeqCst);
+        if ctx.command_type() == CommandType::Read {
+            ctx.set_filter_expression(eq(int_bin("tenant".to_string()), int_val(1)));
+        }
+        Ok(())
+    }
+
+    fn after_command(&self, ctx: &CommandContext, _result: &Result<()>) {
+        assert!(ctx.node().is_some());
+        self.after.fetch_add(1, Ordering::SeqCst);
+        self.keys.fetch_add(ctx.keys().len(), Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug)]
+struct FailingInterceptor;
+
+impl Interceptor for FailingInterceptor {
+    fn before_command(&self, _ctx: &mut CommandContext) -> Result<()> {
+        Err(ErrorKind::Connection("injected failure".to_string()).into())
+    }
+}
+
+#[test]
+fn interceptor_hooks() {
+    let _ = env_logger::try_init();
+
+    let interceptor = Arc::new(TenantInterceptor::default());
+    let mut policy = common::client_policy().clone();
+    policy.interceptors.push(interceptor.clone());
+    let client = Client::new(&policy, &common::hosts()).unwrap();
+
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key1 = as_key!(namespace, set_name, 1);
+    let key2 = as_key!(namespace, set_name, 2);
+    let wpolicy = WritePolicy::default();
+    client
+        .put(&wpolicy, &key1, &[as_bin!("tenant", 1)])
+        .unwrap();
+    client
+        .put(&wpolicy, &key2, &[as_bin!("tenant", 2)])
+        .unwrap();
+
+    // reads are restricted to the records of tenant 1
+    let rpolicy = ReadPolicy::default();
+    client.get(&rpolicy, &key1, Bins::All).unwrap();
+    assert!(client.get(&rpolicy, &key2, Bins::All).is_err());
+
+    let bins = Bins::All;
+    let batch = vec![
+        BatchRead::new(key1.clone(), &bins),
+        BatchRead::new(key2.clone(), &bins),
+    ];
+    client.batch_get(&BatchPolicy::default(), batch).unwrap();
+
+    let before = interceptor.before.load(Ordering::SeqCst);
+    assert!(before >= 5);
+    assert_eq!(interceptor.after.load(Ordering::SeqCst), before);
+    assert_eq!(interceptor.keys.load(Ordering::SeqCst), 6);
+    client.close().unwrap();
+
+    let mut policy = common::client_policy().clone();
+    policy.interceptors.push(Arc::new(FailingInterceptor));
+    let client = Client::new(&policy, &common::hosts()).unwrap();
+    let err = client.get(&rpolicy, &key1, Bins::All).unwrap_err();
+    match err.kind() {
+        ErrorKind::Connection(msg) => assert_eq!(msg, "injected failure"),
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    // scans and queries deliver the error through the recordset
+    let recordset = client
+        .scan(&ScanPolicy::default(), namespace, set_name, Bins::All)
+        .unwrap();
+    assert_injected_failure(&recordset);
+    let stmt = Statement::new(namespace, set_name, Bins::All);
+    let recordset = client.query(&QueryPolicy::default(), stmt).unwrap();
+    assert_injected_failure(&recordset);
+    client.close().unwrap();
+}
+
+fn assert_injected_failure(recordset: &Recordset) {
+    let results: Vec<_> = recordset.into_iter().collect();
+    assert!(!results.is_empty());
+    for result in results {
+        match result {
+            Err(err) => match err.kind() {
+                ErrorKind::Connection(msg) => assert_eq!(msg, "injected failure"),
+                _ => panic!("unexpected error: {}", err),
+            },
+            Ok(record) => panic!("unexpected record: {}", record),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use aerospike::{as_key, Bins, ReadPolicy, WritePolicy};
+use serde_json::json;
+
+use crate::common;
+
+#[test]
+fn put_get_json() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, "json");
+
+    let doc = json!({
+        "name": "Jane",
+        "age": 42,
+        "score": 1.5,
+        "profile": {
+            "active": true,
+            "addresses": [{"city": "Berlin", "zip": null}],
+            "big": u64::MAX,
+        },
+    });
+    client
+        .put_json(&WritePolicy::default(), &key, &doc)
+        .unwrap();
+
+    let read = client
+        .get_json(&ReadPolicy::default(), &key, Bins::All)
+        .unwrap();
+    assert_eq!(read, doc);
+
+    let read = client
+        .get_json(&ReadPolicy::default(), &key, ["name"])
+        .unwrap();
+    assert_eq!(read, json!({"name": "Jane"}));
+
+    assert!(client
+        .put_json(&WritePolicy::default(), &key, &json!([1, 2]))
+        .is_err());
+    assert!(client
+        .put_json(&WritePolicy::default(), &key, &json!({ "big": u64::MAX }))
+        .is_err());
+}
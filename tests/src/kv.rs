@@ -12,9 +12,13 @@
 // WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
 // License for the specific language governing permissions and limitations under
 // the License.
+use std::sync::Arc;
+
 use aerospike::operations;
+use aerospike::policy::ExponentialBackoff;
 use aerospike::{
-    as_bin, as_blob, as_geo, as_key, as_list, as_map, as_val, Bins, ReadPolicy, Value, WritePolicy,
+    as_bin, as_blob, as_geo, as_key, as_list, as_map, as_val, Bins, ReadPolicy, ResultCode, Value,
+    WritePolicy,
 };
 use env_logger;
 
@@ -98,3 +102,74 @@ fn connect() {
     let existed = client.delete(&wpolicy, &key).unwrap();
     assert!(!existed);
 }
+
+#[test]
+fn bool_bins() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let policy = ReadPolicy::default();
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+
+    let bins = [as_bin!("true", true), as_bin!("false", false)];
+    client.put(&wpolicy, &key, &bins).unwrap();
+
+    let bin = as_bin!("op", true);
+    let ops = &vec![operations::put(&bin)];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    // Servers without native boolean support return the values as integers.
+    let record = client.get(&policy, &key, Bins::All).unwrap();
+    assert!(record.get::<bool>("true").unwrap());
+    assert!(!record.get::<bool>("false").unwrap());
+    assert!(record.get::<bool>("op").unwrap());
+}
+
+#[test]
+fn error_context() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, "missing");
+
+    let err = client
+        .get(&ReadPolicy::default(), &key, Bins::All)
+        .unwrap_err();
+    assert_eq!(err.result_code(), Some(ResultCode::KeyNotFoundError));
+    assert!(err.node().is_some());
+    assert_eq!(err.iterations(), 1);
+    assert!(!err.in_doubt());
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn retry_policy() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, "missing");
+
+    // KeyNotFoundError is not in the default retry list, so the command fails on the first try.
+    let mut policy = ReadPolicy::default();
+    policy.retry_policy = Some(Arc::new(ExponentialBackoff::default()));
+    let err = client.get(&policy, &key, Bins::All).unwrap_err();
+    assert_eq!(err.result_code(), Some(ResultCode::KeyNotFoundError));
+    assert_eq!(err.iterations(), 1);
+
+    // Retrying on the result code exhausts the retries and returns the last error.
+    policy.retry_policy = Some(Arc::new(ExponentialBackoff {
+        max_retries: 2,
+        retry_on: vec![ResultCode::KeyNotFoundError],
+        ..ExponentialBackoff::default()
+    }));
+    let err = client.get(&policy, &key, Bins::All).unwrap_err();
+    assert_eq!(err.result_code(), Some(ResultCode::KeyNotFoundError));
+    assert_eq!(err.iterations(), 3);
+}
@@ -0,0 +1,135 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::Arc;
+
+use aerospike::metrics::{CommandType, LatencyMetrics};
+use aerospike::{as_bin, as_key, Bins, Client, ControlFlow, ReadPolicy, ScanPolicy, WritePolicy};
+use env_logger;
+
+use crate::common;
+
+#[test]
+fn latency_metrics() {
+    let _ = env_logger::try_init();
+
+    let metrics = Arc::new(LatencyMetrics::new());
+    let mut policy = common::client_policy().clone();
+    policy.metrics_listener = Some(metrics.clone());
+    let client = Client::new(&policy, &common::hosts()).unwrap();
+
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, 1);
+    client
+        .put(&WritePolicy::default(), &key, &[as_bin!("a", 1)])
+        .unwrap();
+    client.get(&ReadPolicy::default(), &key, Bins::All).unwrap();
+    let missing = as_key!(namespace, set_name, 2);
+    client
+        .get(&ReadPolicy::default(), &missing, Bins::All)
+        .unwrap_err();
+
+    let writes = metrics.total(CommandType::Write);
+    assert_eq!(writes.count(), 1);
+    assert_eq!(writes.errors(), 0);
+
+    let reads = metrics.total(CommandType::Read);
+    assert_eq!(reads.count(), 2);
+    assert_eq!(reads.errors(), 1);
+    assert!(metrics
+        .histograms()
+        .iter()
+        .all(|(_, node, _)| client.get_node(node).is_ok()));
+
+    client.close().unwrap();
+}
+
+#[test]
+fn scan_break_is_not_an_error() {
+    let _ = env_logger::try_init();
+
+    let metrics = Arc::new(LatencyMetrics::new());
+    let mut policy = common::client_policy().clone();
+    policy.metrics_listener = Some(metrics.clone());
+    let client = Client::new(&policy, &common::hosts()).unwrap();
+
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    for i in 0..100 {
+        let key = as_key!(namespace, set_name, i);
+        client
+            .put(&WritePolicy::default(), &key, &[as_bin!("a", i)])
+            .unwrap();
+    }
+
+    client
+        .scan_with(
+            &ScanPolicy::default(),
+            namespace,
+            set_name,
+            Bins::All,
+            |_| ControlFlow::Break,
+        )
+        .unwrap();
+
+    let scans = metrics.total(CommandType::Scan);
+    assert!(scans.count() > 0);
+    assert_eq!(scans.errors(), 0);
+
+    client.close().unwrap();
+}
+
+#[test]
+fn client_stats() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    // errors returned by the server do not count towards the node error rate
+    let missing = as_key!(namespace, set_name, 1);
+    let err = client
+        .get(&ReadPolicy::default(), &missing, Bins::All)
+        .unwrap_err();
+    assert!(!err.is_retryable());
+
+    let stats = client.stats();
+    assert_eq!(stats.nodes.len(), client.nodes().len());
+    for node in &stats.nodes {
+        assert!(client.get_node(&node.name).is_ok());
+    }
+    assert_eq!(stats.error_rate_trips(), 0);
+}
+
+#[test]
+fn warm_up() {
+    let _ = env_logger::try_init();
+
+    let mut policy = common::client_policy().clone();
+    policy.min_conns_per_node = 2;
+    let client = Client::new(&policy, &common::hosts()).unwrap();
+
+    // the pools are pre-warmed when the nodes are added
+    let stats = client.stats();
+    assert!(stats.nodes.iter().all(|node| node.connections >= 2));
+
+    client.warm_up(8).unwrap();
+    let stats = client.stats();
+    assert!(stats.nodes.iter().all(|node| node.connections >= 8));
+
+    client.close().unwrap();
+}
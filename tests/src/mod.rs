@@ -17,6 +17,9 @@ mod batch;
 mod cdt_bitwise;
 mod cdt_list;
 mod cdt_map;
+#[cfg(feature = "derive")]
+mod derive;
+mod document;
 mod exp;
 mod exp_bitwise;
 mod exp_hll;
@@ -25,11 +28,17 @@ mod exp_map;
 mod exp_op;
 mod hll;
 mod index;
+mod info;
+mod interceptor;
+#[cfg(feature = "json")]
+mod json;
 mod kv;
+mod metrics;
 mod query;
 mod scan;
 #[cfg(feature = "serialization")]
 mod serialization;
 mod task;
+mod transport;
 mod truncate;
 mod udf;
@@ -13,6 +13,7 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::convert::TryFrom;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -88,7 +89,7 @@ fn query_single_consumer() {
         match res {
             Ok(rec) => {
                 count += 1;
-                let v: i64 = rec.bins["bin"].clone().into();
+                let v = i64::try_from(rec.bins["bin"].clone()).unwrap();
                 assert!(v >= 0);
                 assert!(v < 10);
             }
@@ -150,7 +151,7 @@ fn query_multi_consumer() {
                 match res {
                     Ok(rec) => {
                         count.fetch_add(1, Ordering::Relaxed);
-                        let v: i64 = rec.bins["bin"].clone().into();
+                        let v = i64::try_from(rec.bins["bin"].clone()).unwrap();
                         assert!(v >= 0);
                         assert!(v < 10);
                     }
@@ -198,3 +199,50 @@ fn query_node() {
 
     assert_eq!(count.load(Ordering::Relaxed), 100);
 }
+
+#[test]
+fn query_borrowed() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let qpolicy = QueryPolicy::default();
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let count = AtomicUsize::new(0);
+    client
+        .query_borrowed(&qpolicy, statement, |rec| {
+            let rec = rec.to_record().unwrap();
+            let val: i64 = rec.get("bin").unwrap();
+            assert!(val >= 0 && val < 10);
+            count.fetch_add(1, Ordering::Relaxed);
+            ControlFlow::Continue
+        })
+        .unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn query_with() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let qpolicy = QueryPolicy::default();
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let count = AtomicUsize::new(0);
+    client
+        .query_with(&qpolicy, statement, |rec| {
+            let val: i64 = rec.get("bin").unwrap();
+            assert!(val >= 0 && val < 10);
+            count.fetch_add(1, Ordering::Relaxed);
+            ControlFlow::Continue
+        })
+        .unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 10);
+}
@@ -122,3 +122,143 @@ fn scan_node() {
 
     assert_eq!(count.load(Ordering::Relaxed), EXPECTED);
 }
+
+#[test]
+fn scan_borrowed() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let spolicy = ScanPolicy::default();
+    let count = AtomicUsize::new(0);
+    let sum = AtomicUsize::new(0);
+    client
+        .scan_borrowed(&spolicy, namespace, &set_name, Bins::All, |rec| {
+            assert_eq!(rec.namespace(), namespace);
+            let val = rec.get("bin").and_then(|val| val.as_i64()).unwrap();
+            sum.fetch_add(val as usize, Ordering::Relaxed);
+            count.fetch_add(1, Ordering::Relaxed);
+            ControlFlow::Continue
+        })
+        .unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), EXPECTED);
+    assert_eq!(sum.load(Ordering::Relaxed), EXPECTED * (EXPECTED - 1) / 2);
+
+    let count = AtomicUsize::new(0);
+    client
+        .scan_borrowed(&spolicy, namespace, &set_name, Bins::All, |_| {
+            if count.fetch_add(1, Ordering::Relaxed) + 1 >= 10 {
+                ControlFlow::Break
+            } else {
+                ControlFlow::Continue
+            }
+        })
+        .unwrap();
+    assert!(count.load(Ordering::Relaxed) < EXPECTED);
+}
+
+#[test]
+fn scan_with() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let spolicy = ScanPolicy::default();
+    let count = AtomicUsize::new(0);
+    client
+        .scan_with(&spolicy, namespace, &set_name, Bins::All, |rec| {
+            assert!(rec.key.is_some());
+            assert!(rec.bins.contains_key("bin"));
+            count.fetch_add(1, Ordering::Relaxed);
+            ControlFlow::Continue
+        })
+        .unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), EXPECTED);
+
+    let count = AtomicUsize::new(0);
+    client
+        .scan_with(&spolicy, namespace, &set_name, Bins::All, |_| {
+            count.fetch_add(1, Ordering::Relaxed);
+            ControlFlow::Break
+        })
+        .unwrap();
+    let count = count.load(Ordering::Relaxed);
+    assert!(count < EXPECTED);
+}
+
+#[test]
+fn scan_close() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.record_queue_size = 10;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+
+    let count = (&*rs).take(5).filter(Result::is_ok).count();
+    assert_eq!(count, 5);
+
+    rs.close();
+    assert!(!rs.is_active());
+
+    // Only records queued before the scan was closed may still be returned.
+    let remaining = (&*rs).count();
+    assert!(remaining <= spolicy.record_queue_size);
+}
+
+#[test]
+fn scan_drop() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.record_queue_size = 10;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    assert!((&*rs).next().is_some());
+    drop(rs);
+
+    // The client must still be usable after the scan has been aborted.
+    let rs = client
+        .scan(&ScanPolicy::default(), namespace, &set_name, Bins::All)
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, EXPECTED);
+}
+
+#[test]
+fn scan_jobs() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.record_queue_size = 10;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    assert!((&*rs).next().is_some());
+
+    let trid = rs.task_id();
+    let jobs = client.jobs().unwrap();
+    assert!(jobs.iter().any(|job| job.trid == trid));
+
+    client.set_job_priority(trid, &Priority::Low).unwrap();
+    client.kill_job(trid).unwrap();
+    assert!(client.kill_job(0).is_err());
+}
@@ -0,0 +1,74 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use aerospike::transport::{Stream, TcpTransport, Transport};
+use aerospike::{as_bin, as_key, Bins, Client, Host, ReadPolicy, WritePolicy};
+use env_logger;
+
+use crate::common;
+
+#[derive(Debug, Default)]
+struct CountingTransport {
+    connects: AtomicUsize,
+}
+
+impl Transport for CountingTransport {
+    fn connect(&self, host: &Host, timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+        self.connects.fetch_add(1, Ordering::SeqCst);
+        TcpTransport.connect(host, timeout)
+    }
+}
+
+#[derive(Debug)]
+struct FailingTransport;
+
+impl Transport for FailingTransport {
+    fn connect(&self, _host: &Host, _timeout: Option<Duration>) -> io::Result<Box<dyn Stream>> {
+        Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            "injected fault",
+        ))
+    }
+}
+
+#[test]
+fn custom_transport() {
+    let _ = env_logger::try_init();
+
+    let transport = Arc::new(CountingTransport::default());
+    let mut policy = common::client_policy().clone();
+    policy.transport = transport.clone();
+    let client = Client::new(&policy, &common::hosts()).unwrap();
+
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, 1);
+    client
+        .put(&WritePolicy::default(), &key, &[as_bin!("a", 1)])
+        .unwrap();
+    client.get(&ReadPolicy::default(), &key, Bins::All).unwrap();
+    assert!(transport.connects.load(Ordering::SeqCst) > 0);
+    client.close().unwrap();
+
+    // the client does not connect if the transport fails
+    let mut policy = common::client_policy().clone();
+    policy.transport = Arc::new(FailingTransport);
+    assert!(Client::new(&policy, &common::hosts()).is_err());
+}
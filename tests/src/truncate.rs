@@ -13,7 +13,9 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use aerospike::WritePolicy;
+use std::time::Duration;
+
+use aerospike::*;
 
 use crate::common;
 use env_logger;
@@ -30,3 +32,42 @@ fn truncate() {
     let result = client.truncate(&wpolicy, namespace, set_name, 0);
     assert!(result.is_ok());
 }
+
+#[test]
+fn delete_set() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    // the wait for the truncation is not limited by the short command timeout
+    let mut wpolicy = WritePolicy::default();
+    wpolicy.base_policy.timeout = Some(Duration::from_millis(200));
+
+    for i in 0..10 {
+        let key = as_key!(namespace, set_name, i);
+        let bin = as_bin!("bin", i);
+        client.put(&wpolicy, &key, &[&bin]).unwrap();
+    }
+
+    client.delete_set(&wpolicy, namespace, set_name).unwrap();
+
+    // the records are deleted once the truncation is registered, even if the server is still
+    // reclaiming their space
+    let sets = client.sets(namespace).unwrap();
+    assert!(sets
+        .iter()
+        .filter(|set| &set.set_name == set_name)
+        .all(|set| set.truncate_lut > 0));
+    for i in 0..10 {
+        let key = as_key!(namespace, set_name, i);
+        let err = client
+            .get(&ReadPolicy::default(), &key, Bins::All)
+            .unwrap_err();
+        assert_eq!(err.result_code(), Some(ResultCode::KeyNotFoundError));
+    }
+
+    // a set without records has nothing to delete
+    let empty_set = &common::rand_str(10);
+    client.delete_set(&wpolicy, namespace, empty_set).unwrap();
+}